@@ -0,0 +1,147 @@
+use crate::ray::Ray;
+use crate::vector2::Vector2;
+
+/// Axis-aligned bounding box.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb {
+    pub min: Vector2,
+    pub max: Vector2,
+}
+
+impl Aabb {
+    pub fn new(min: Vector2, max: Vector2) -> Self {
+        if min.x > max.x || min.y > max.y {
+            panic!("Minimum corner of bounding box must not exceed the maximum corner");
+        }
+        Aabb { min, max }
+    }
+
+    /// Smallest box containing every point, panics on an empty slice.
+    pub fn from_points(points: &[Vector2]) -> Self {
+        let first = points[0];
+        points.iter().skip(1).fold(
+            Aabb {
+                min: first,
+                max: first,
+            },
+            |bounds, point| Aabb {
+                min: Vector2::new(bounds.min.x.min(point.x), bounds.min.y.min(point.y)),
+                max: Vector2::new(bounds.max.x.max(point.x), bounds.max.y.max(point.y)),
+            },
+        )
+    }
+
+    /// Distance along the ray at which it enters the box, `0.0` if it starts
+    /// inside and `None` if it misses within its length.
+    pub fn raycast(&self, ray: &Ray) -> Option<f64> {
+        let mut enter: f64 = 0.0;
+        let mut exit = ray.max_distance;
+        for (origin, direction, min, max) in [
+            (ray.origin.x, ray.direction.x, self.min.x, self.max.x),
+            (ray.origin.y, ray.direction.y, self.min.y, self.max.y),
+        ] {
+            if direction == 0.0 {
+                if origin < min || max < origin {
+                    return None;
+                }
+                continue;
+            }
+            let near = (min - origin) / direction;
+            let far = (max - origin) / direction;
+            enter = enter.max(near.min(far));
+            exit = exit.min(near.max(far));
+        }
+        if enter <= exit {
+            Some(enter)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the boxes overlap, touching edges don't count.
+    pub fn overlaps(&self, other: &Aabb) -> bool {
+        self.min.x < other.max.x
+            && other.min.x < self.max.x
+            && self.min.y < other.max.y
+            && other.min.y < self.max.y
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aabb::Aabb;
+    use crate::ray::Ray;
+    use crate::vector2::Vector2;
+
+    #[test]
+    #[should_panic]
+    fn new_min_greater_than_max_panic() {
+        Aabb::new(Vector2::new(1.0, 0.0), Vector2::new(0.0, 1.0));
+    }
+
+    #[test]
+    fn from_points_returns_enclosing_box() {
+        let points = [
+            Vector2::new(1.0, -2.0),
+            Vector2::new(-3.0, 4.0),
+            Vector2::new(0.0, 0.0),
+        ];
+
+        let result = Aabb::from_points(&points);
+
+        assert_eq!(
+            result,
+            Aabb::new(Vector2::new(-3.0, -2.0), Vector2::new(1.0, 4.0))
+        );
+    }
+
+    #[test]
+    fn overlaps_overlapping_boxes_returns_true() {
+        let bounds = Aabb::new(Vector2::new(0.0, 0.0), Vector2::new(2.0, 2.0));
+        let other = Aabb::new(Vector2::new(1.0, 1.0), Vector2::new(3.0, 3.0));
+
+        let result = bounds.overlaps(&other);
+
+        assert!(result);
+    }
+
+    #[test]
+    fn overlaps_touching_boxes_returns_false() {
+        let bounds = Aabb::new(Vector2::new(0.0, 0.0), Vector2::new(2.0, 2.0));
+        let other = Aabb::new(Vector2::new(2.0, 0.0), Vector2::new(4.0, 2.0));
+
+        let result = bounds.overlaps(&other);
+
+        assert!(!result);
+    }
+
+    #[test]
+    fn raycast_ray_towards_box_returns_entry_distance() {
+        let bounds = Aabb::new(Vector2::new(2.0, -1.0), Vector2::new(4.0, 1.0));
+        let ray = Ray::new(Vector2::new(0.0, 0.0), Vector2::new(1.0, 0.0), 10.0);
+
+        let result = bounds.raycast(&ray);
+
+        assert_eq!(result, Some(2.0));
+    }
+
+    #[test]
+    fn raycast_ray_too_short_returns_none() {
+        let bounds = Aabb::new(Vector2::new(2.0, -1.0), Vector2::new(4.0, 1.0));
+        let ray = Ray::new(Vector2::new(0.0, 0.0), Vector2::new(1.0, 0.0), 1.0);
+
+        let result = bounds.raycast(&ray);
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn raycast_parallel_ray_outside_box_returns_none() {
+        let bounds = Aabb::new(Vector2::new(2.0, -1.0), Vector2::new(4.0, 1.0));
+        let ray = Ray::new(Vector2::new(0.0, 2.0), Vector2::new(1.0, 0.0), 10.0);
+
+        let result = bounds.raycast(&ray);
+
+        assert!(result.is_none());
+    }
+}
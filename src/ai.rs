@@ -0,0 +1,326 @@
+//! Finite state machine deciding what a chaser is up to.
+//!
+//! A chaser notices the player when it can see them within its sight range,
+//! or when they come within hearing range whether it can see them or not.
+//! What it does then, how long it keeps at it and when it turns tail is up
+//! to its `Personality`. Bumping into something stuns it, whatever it was
+//! doing.
+
+use crate::settings;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AiState {
+    /// Standing around.
+    Idle,
+    /// Wandering about looking for trouble.
+    Patrol,
+    Chase,
+    /// Running away from a player that got too close.
+    Flee,
+    /// Knocked senseless for a moment.
+    Stunned,
+}
+
+/// What a chaser knows about the nearest player this step.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Perception {
+    /// Distance to the player, `None` if there is no player.
+    pub distance: Option<f64>,
+    /// Whether no wall is in the way.
+    pub line_of_sight: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Personality {
+    /// Notices a player it can see this close.
+    pub sight_range: f64,
+    /// Notices a player this close even without seeing them.
+    pub hearing_range: f64,
+    /// Stops chasing a player further away than this.
+    pub give_up_range: f64,
+    /// Seconds it keeps chasing a player it hasn't noticed any more.
+    pub memory: f64,
+    /// Turns and runs from a player closer than this.
+    pub flee_range: f64,
+    /// Seconds it runs before thinking again.
+    pub flee_time: f64,
+    pub idle_time: f64,
+    pub patrol_time: f64,
+    pub stun_time: f64,
+}
+
+impl Personality {
+    /// Always knows where the player is and never lets go.
+    pub fn relentless() -> Self {
+        Personality {
+            sight_range: f64::INFINITY,
+            hearing_range: f64::INFINITY,
+            give_up_range: f64::INFINITY,
+            memory: f64::INFINITY,
+            flee_range: 0.0,
+            flee_time: 0.0,
+            idle_time: settings::ai::IDLE_TIME,
+            patrol_time: settings::ai::PATROL_TIME,
+            stun_time: settings::ai::STUN_TIME,
+        }
+    }
+
+    /// Patrols until it spots the player, then hangs on for a while after
+    /// losing sight of them.
+    pub fn stalker() -> Self {
+        Personality {
+            sight_range: 400.0,
+            hearing_range: 120.0,
+            give_up_range: 600.0,
+            memory: 2.0,
+            flee_range: 0.0,
+            flee_time: 0.0,
+            idle_time: settings::ai::IDLE_TIME,
+            patrol_time: settings::ai::PATROL_TIME,
+            stun_time: settings::ai::STUN_TIME,
+        }
+    }
+
+    /// Closes in, loses its nerve up close and backs off before trying
+    /// again.
+    pub fn skittish() -> Self {
+        Personality {
+            sight_range: 350.0,
+            hearing_range: 150.0,
+            give_up_range: 500.0,
+            memory: 1.0,
+            flee_range: 90.0,
+            flee_time: 1.5,
+            idle_time: settings::ai::IDLE_TIME,
+            patrol_time: settings::ai::PATROL_TIME,
+            stun_time: settings::ai::STUN_TIME,
+        }
+    }
+
+    fn notices(&self, perception: &Perception) -> bool {
+        perception.distance.is_some_and(|distance| {
+            distance <= self.hearing_range
+                || (perception.line_of_sight && distance <= self.sight_range)
+        })
+    }
+}
+
+pub struct Brain {
+    personality: Personality,
+    state: AiState,
+    /// Seconds since entering the current state.
+    time_in_state: f64,
+    /// Seconds since the player was last noticed.
+    time_unnoticed: f64,
+}
+
+impl Brain {
+    /// Brain that starts out idle.
+    pub fn new(personality: Personality) -> Self {
+        Brain {
+            personality,
+            state: AiState::Idle,
+            time_in_state: 0.0,
+            time_unnoticed: 0.0,
+        }
+    }
+
+    pub fn get_state(&self) -> AiState {
+        self.state
+    }
+
+    pub fn get_personality(&self) -> &Personality {
+        &self.personality
+    }
+
+    /// Knocks the chaser senseless, starting the stun over if it already is.
+    pub fn stun(&mut self) {
+        self.enter(AiState::Stunned);
+    }
+
+    /// Advances the timers by `dt` and changes state if it is time to.
+    pub fn think(&mut self, perception: Perception, dt: f64) {
+        self.time_in_state += dt;
+        let notices = self.personality.notices(&perception);
+        if notices {
+            self.time_unnoticed = 0.0;
+        } else {
+            self.time_unnoticed += dt;
+        }
+        let distance = perception.distance.unwrap_or(f64::INFINITY);
+        let personality = &self.personality;
+
+        let next = match self.state {
+            AiState::Idle | AiState::Patrol if notices => Some(AiState::Chase),
+            AiState::Idle if self.time_in_state >= personality.idle_time => Some(AiState::Patrol),
+            AiState::Patrol if self.time_in_state >= personality.patrol_time => Some(AiState::Idle),
+            AiState::Chase if distance < personality.flee_range => Some(AiState::Flee),
+            AiState::Chase
+                if distance > personality.give_up_range
+                    || self.time_unnoticed > personality.memory =>
+            {
+                Some(AiState::Idle)
+            }
+            AiState::Flee | AiState::Stunned if self.time_in_state >= self.timeout() => {
+                Some(if notices {
+                    AiState::Chase
+                } else {
+                    AiState::Idle
+                })
+            }
+            _ => None,
+        };
+        if let Some(state) = next {
+            self.enter(state);
+        }
+    }
+
+    fn timeout(&self) -> f64 {
+        match self.state {
+            AiState::Flee => self.personality.flee_time,
+            AiState::Stunned => self.personality.stun_time,
+            _ => f64::INFINITY,
+        }
+    }
+
+    fn enter(&mut self, state: AiState) {
+        self.state = state;
+        self.time_in_state = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ai::{AiState, Brain, Perception, Personality};
+
+    fn seen_at(distance: f64) -> Perception {
+        Perception {
+            distance: Some(distance),
+            line_of_sight: true,
+        }
+    }
+
+    fn hidden_at(distance: f64) -> Perception {
+        Perception {
+            distance: Some(distance),
+            line_of_sight: false,
+        }
+    }
+
+    fn stalker() -> Brain {
+        Brain::new(Personality::stalker())
+    }
+
+    #[test]
+    fn think_visible_player_in_sight_range_starts_chase() {
+        let mut brain = stalker();
+
+        brain.think(seen_at(300.0), 0.1);
+
+        assert_eq!(brain.get_state(), AiState::Chase);
+    }
+
+    #[test]
+    fn think_player_behind_wall_out_of_hearing_stays_idle() {
+        let mut brain = stalker();
+
+        brain.think(hidden_at(300.0), 0.1);
+
+        assert_eq!(brain.get_state(), AiState::Idle);
+    }
+
+    #[test]
+    fn think_player_behind_wall_in_hearing_range_starts_chase() {
+        let mut brain = stalker();
+
+        brain.think(hidden_at(100.0), 0.1);
+
+        assert_eq!(brain.get_state(), AiState::Chase);
+    }
+
+    #[test]
+    fn think_idle_long_enough_starts_patrol_then_idles_again() {
+        let mut brain = stalker();
+        let personality = *brain.get_personality();
+
+        brain.think(seen_at(1000.0), personality.idle_time);
+        let patrolling = brain.get_state();
+        brain.think(seen_at(1000.0), personality.patrol_time);
+
+        assert_eq!(patrolling, AiState::Patrol);
+        assert_eq!(brain.get_state(), AiState::Idle);
+    }
+
+    #[test]
+    fn think_chase_keeps_going_until_memory_runs_out() {
+        let mut brain = stalker();
+        brain.think(seen_at(300.0), 0.1);
+
+        brain.think(hidden_at(300.0), 1.5);
+        let remembering = brain.get_state();
+        brain.think(hidden_at(300.0), 1.0);
+
+        assert_eq!(remembering, AiState::Chase);
+        assert_eq!(brain.get_state(), AiState::Idle);
+    }
+
+    #[test]
+    fn think_chase_player_beyond_give_up_range_idles() {
+        let mut brain = stalker();
+        brain.think(seen_at(300.0), 0.1);
+
+        brain.think(seen_at(700.0), 0.1);
+
+        assert_eq!(brain.get_state(), AiState::Idle);
+    }
+
+    #[test]
+    fn think_skittish_too_close_flees_then_chases_again() {
+        let mut brain = Brain::new(Personality::skittish());
+        brain.think(seen_at(300.0), 0.1);
+
+        brain.think(seen_at(50.0), 0.1);
+        let fleeing = brain.get_state();
+        brain.think(seen_at(200.0), brain.get_personality().flee_time);
+
+        assert_eq!(fleeing, AiState::Flee);
+        assert_eq!(brain.get_state(), AiState::Chase);
+    }
+
+    #[test]
+    fn think_relentless_notices_player_anywhere() {
+        let mut brain = Brain::new(Personality::relentless());
+
+        brain.think(hidden_at(5000.0), 0.1);
+
+        assert_eq!(brain.get_state(), AiState::Chase);
+    }
+
+    #[test]
+    fn stun_holds_until_stun_time_passes() {
+        let mut brain = stalker();
+        brain.think(seen_at(300.0), 0.1);
+        let stun_time = brain.get_personality().stun_time;
+
+        brain.stun();
+        brain.think(seen_at(300.0), stun_time / 2.0);
+        let stunned = brain.get_state();
+        brain.think(seen_at(300.0), stun_time / 2.0);
+
+        assert_eq!(stunned, AiState::Stunned);
+        assert_eq!(brain.get_state(), AiState::Chase);
+    }
+
+    #[test]
+    fn think_without_player_does_not_chase() {
+        let mut brain = Brain::new(Personality::relentless());
+        let nobody = Perception {
+            distance: None,
+            line_of_sight: false,
+        };
+
+        brain.think(nobody, 0.1);
+
+        assert_eq!(brain.get_state(), AiState::Idle);
+    }
+}
@@ -0,0 +1,166 @@
+use crate::collider::Collider;
+use crate::collides::Collides;
+use crate::layer;
+use crate::manifold::Manifold;
+use crate::settings;
+use crate::vector2::Vector2;
+
+/// Static walls that solid colliders get pushed back out of.
+pub struct Arena {
+    walls: Vec<Collider>,
+}
+
+impl Arena {
+    /// Arena enclosed by walls lying just outside a `width` by `height` area
+    /// with its corner at the origin.
+    pub fn new(width: f64, height: f64) -> Self {
+        let thickness = settings::arena::WALL_THICKNESS;
+        let half = thickness / 2.0;
+        let mut arena = Arena { walls: Vec::new() };
+        // The horizontal walls overhang the corners so nothing slips through.
+        arena.add_wall(Collider::new_box(
+            Vector2::new(width / 2.0, -half),
+            width / 2.0 + thickness,
+            half,
+            0.0,
+        ));
+        arena.add_wall(Collider::new_box(
+            Vector2::new(width / 2.0, height + half),
+            width / 2.0 + thickness,
+            half,
+            0.0,
+        ));
+        arena.add_wall(Collider::new_box(
+            Vector2::new(-half, height / 2.0),
+            half,
+            height / 2.0,
+            0.0,
+        ));
+        arena.add_wall(Collider::new_box(
+            Vector2::new(width + half, height / 2.0),
+            half,
+            height / 2.0,
+            0.0,
+        ));
+        arena
+    }
+
+    pub fn add_wall(&mut self, mut wall: Collider) {
+        wall.set_layer(layer::WALL);
+        wall.set_mask(layer::ALL);
+        self.walls.push(wall);
+    }
+
+    pub fn walls(&self) -> &[Collider] {
+        &self.walls
+    }
+
+    /// Pushes the body out of every wall it overlaps, deepest contact first.
+    /// Only the part of the motion going into a wall is undone, so bodies
+    /// slide along walls instead of sticking to them. Returns the contacts
+    /// that were resolved, normals pointing into the walls.
+    pub fn resolve<C: Collides>(&self, body: &mut C) -> Vec<Manifold> {
+        let mut contacts = Vec::new();
+        if body.get_collider().is_trigger() {
+            return contacts;
+        }
+        for _ in 0..settings::arena::RESOLVE_ITERATIONS {
+            let collider = body.get_collider();
+            let deepest = self
+                .walls
+                .iter()
+                .filter_map(|wall| collider.contact(wall))
+                .max_by(|a, b| a.depth.partial_cmp(&b.depth).unwrap());
+            match deepest {
+                Some(manifold) => {
+                    let position = collider.get_position() + manifold.minimum_translation();
+                    collider.set_position(position);
+                    contacts.push(manifold);
+                }
+                None => break,
+            }
+        }
+        contacts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::arena::Arena;
+    use crate::collider::{Collider, ColliderKind};
+    use crate::collides::Collides;
+    use crate::vector2::Vector2;
+
+    struct Body {
+        collider: Collider,
+    }
+
+    impl Collides for Body {
+        fn get_collider(&mut self) -> &mut Collider {
+            &mut self.collider
+        }
+    }
+
+    fn body(x: f64, y: f64) -> Body {
+        Body {
+            collider: Collider::new(Vector2::new(x, y), 10.0),
+        }
+    }
+
+    #[test]
+    fn resolve_body_inside_arena_stays_put() {
+        let arena = Arena::new(100.0, 50.0);
+        let mut body = body(50.0, 25.0);
+
+        let result = arena.resolve(&mut body);
+
+        assert!(result.is_empty());
+        assert_eq!(body.collider.get_position(), Vector2::new(50.0, 25.0));
+    }
+
+    #[test]
+    fn resolve_body_past_right_edge_slides_along_wall() {
+        let arena = Arena::new(100.0, 50.0);
+        // Moved diagonally from (85, 20) into the right wall.
+        let mut body = body(95.0, 30.0);
+
+        let result = arena.resolve(&mut body);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].normal, Vector2::new(1.0, 0.0));
+        assert_eq!(body.collider.get_position(), Vector2::new(90.0, 30.0));
+    }
+
+    #[test]
+    fn resolve_body_in_corner_pushed_out_of_both_walls() {
+        let arena = Arena::new(100.0, 50.0);
+        let mut body = body(-5.0, 55.0);
+
+        arena.resolve(&mut body);
+
+        assert_eq!(body.collider.get_position(), Vector2::new(10.0, 40.0));
+    }
+
+    #[test]
+    fn resolve_body_against_added_wall_pushed_out() {
+        let mut arena = Arena::new(100.0, 50.0);
+        arena.add_wall(Collider::new_box(Vector2::new(50.0, 25.0), 5.0, 5.0, 0.0));
+        let mut body = body(62.0, 25.0);
+
+        arena.resolve(&mut body);
+
+        assert_eq!(body.collider.get_position(), Vector2::new(65.0, 25.0));
+    }
+
+    #[test]
+    fn resolve_trigger_is_not_blocked() {
+        let arena = Arena::new(100.0, 50.0);
+        let mut body = body(95.0, 25.0);
+        body.collider.set_kind(ColliderKind::Trigger);
+
+        let result = arena.resolve(&mut body);
+
+        assert!(result.is_empty());
+        assert_eq!(body.collider.get_position(), Vector2::new(95.0, 25.0));
+    }
+}
@@ -0,0 +1,275 @@
+//! Uniform grid broad phase.
+//!
+//! Colliders are bucketed into square cells by their bounding boxes, and only
+//! colliders sharing a cell with overlapping bounds are handed on to the
+//! narrow phase. The cell size should be around the size of a typical
+//! collider: much smaller and large colliders span many cells, much larger
+//! and every cell holds most of the scene. Pairs whose collision layers
+//! don't interact are never reported.
+
+use crate::aabb::Aabb;
+use crate::collider::Collider;
+use crate::layer;
+use crate::vector2::Vector2;
+use std::collections::HashMap;
+
+struct Entry {
+    id: usize,
+    bounds: Aabb,
+    layer: u32,
+    mask: u32,
+}
+
+pub struct SpatialHash {
+    cell_size: f64,
+    cells: HashMap<(i64, i64), Vec<usize>>,
+    entries: Vec<Entry>,
+}
+
+impl SpatialHash {
+    pub fn new(cell_size: f64) -> Self {
+        if cell_size <= 0.0 {
+            panic!("Cell size of spatial hash must be greater than 0");
+        }
+        SpatialHash {
+            cell_size,
+            cells: HashMap::new(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Removes every collider. Cells that were in use keep their allocations
+    /// for the next frame, cells left empty since the last clear are dropped
+    /// so the map doesn't keep every cell a collider ever passed through.
+    pub fn clear(&mut self) {
+        self.cells.retain(|_, cell| {
+            let used = !cell.is_empty();
+            cell.clear();
+            used
+        });
+        self.entries.clear();
+    }
+
+    pub fn insert(&mut self, id: usize, collider: &Collider) {
+        self.insert_swept(id, collider, Vector2::new(0.0, 0.0));
+    }
+
+    /// Inserts the area the collider swept through on its way to where it
+    /// is now after moving by `motion`.
+    pub fn insert_swept(&mut self, id: usize, collider: &Collider, motion: Vector2) {
+        let end = collider.bounds();
+        let bounds = Aabb::from_points(&[end.min, end.max, end.min - motion, end.max - motion]);
+        let entry = self.entries.len();
+        self.entries.push(Entry {
+            id,
+            bounds,
+            layer: collider.get_layer(),
+            mask: collider.get_mask(),
+        });
+
+        let (min_x, min_y) = self.cell(bounds.min.x, bounds.min.y);
+        let (max_x, max_y) = self.cell(bounds.max.x, bounds.max.y);
+        for x in min_x..=max_x {
+            for y in min_y..=max_y {
+                self.cells.entry((x, y)).or_default().push(entry);
+            }
+        }
+    }
+
+    /// Pairs of ids whose bounding boxes overlap, each pair once with the
+    /// smaller id first, in ascending order.
+    pub fn candidate_pairs(&self) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        for cell in self.cells.values() {
+            for (i, &entry) in cell.iter().enumerate() {
+                let entry = &self.entries[entry];
+                for &other_entry in &cell[i + 1..] {
+                    let other = &self.entries[other_entry];
+                    if layer::interacts(entry.layer, entry.mask, other.layer, other.mask)
+                        && entry.bounds.overlaps(&other.bounds)
+                    {
+                        pairs.push((entry.id.min(other.id), entry.id.max(other.id)));
+                    }
+                }
+            }
+        }
+        pairs.sort_unstable();
+        pairs.dedup();
+        pairs
+    }
+
+    /// Candidate pairs that really overlap according to the narrow phase.
+    /// Ids are expected to be indices into `colliders`.
+    pub fn colliding_pairs(&self, colliders: &[&Collider]) -> Vec<(usize, usize)> {
+        self.candidate_pairs()
+            .into_iter()
+            .filter(|&(a, b)| colliders[a].collides_with(colliders[b]))
+            .collect()
+    }
+
+    fn cell(&self, x: f64, y: f64) -> (i64, i64) {
+        (
+            (x / self.cell_size).floor() as i64,
+            (y / self.cell_size).floor() as i64,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::broad_phase::SpatialHash;
+    use crate::collider::Collider;
+    use crate::layer;
+    use crate::vector2::Vector2;
+
+    /// Xorshift generator so the randomized scenes are reproducible.
+    struct Random(u64);
+
+    impl Random {
+        fn next(&mut self) -> f64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 >> 11) as f64 / (1u64 << 53) as f64
+        }
+
+        fn range(&mut self, min: f64, max: f64) -> f64 {
+            min + self.next() * (max - min)
+        }
+    }
+
+    fn random_scene(random: &mut Random, count: usize) -> Vec<Collider> {
+        (0..count)
+            .map(|_| {
+                let position =
+                    Vector2::new(random.range(-200.0, 600.0), random.range(-50.0, 300.0));
+                let mut collider = if random.next() < 0.5 {
+                    Collider::new(position, random.range(1.0, 40.0))
+                } else {
+                    let half_width = random.range(1.0, 60.0);
+                    let half_height = random.range(1.0, 30.0);
+                    let rotation = random.range(0.0, std::f64::consts::PI);
+                    Collider::new_box(position, half_width, half_height, rotation)
+                };
+                if random.next() < 0.5 {
+                    collider.set_layer(layer::ENEMY);
+                    collider.set_mask(layer::PLAYER);
+                }
+                collider
+            })
+            .collect()
+    }
+
+    fn brute_force_pairs(colliders: &[&Collider]) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        for a in 0..colliders.len() {
+            for b in a + 1..colliders.len() {
+                if colliders[a].collides_with(colliders[b]) {
+                    pairs.push((a, b));
+                }
+            }
+        }
+        pairs
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_cell_size_less_than_equal_zero_panic() {
+        SpatialHash::new(0.0);
+    }
+
+    #[test]
+    fn candidate_pairs_far_apart_colliders_returns_no_pairs() {
+        let mut hash = SpatialHash::new(10.0);
+        hash.insert(0, &Collider::new(Vector2::new(0.0, 0.0), 1.0));
+        hash.insert(1, &Collider::new(Vector2::new(100.0, 0.0), 1.0));
+
+        let result = hash.candidate_pairs();
+
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn candidate_pairs_colliders_sharing_many_cells_returns_pair_once() {
+        let mut hash = SpatialHash::new(1.0);
+        hash.insert(7, &Collider::new(Vector2::new(0.0, 0.0), 5.0));
+        hash.insert(3, &Collider::new(Vector2::new(1.0, 1.0), 5.0));
+
+        let result = hash.candidate_pairs();
+
+        assert_eq!(result, vec![(3, 7)]);
+    }
+
+    #[test]
+    fn candidate_pairs_non_interacting_layers_returns_no_pairs() {
+        let mut chaser = Collider::new(Vector2::new(0.0, 0.0), 1.0);
+        chaser.set_layer(layer::ENEMY);
+        chaser.set_mask(layer::PLAYER);
+        let mut hash = SpatialHash::new(10.0);
+        hash.insert(0, &chaser);
+        hash.insert(1, &chaser);
+
+        let result = hash.candidate_pairs();
+
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn candidate_pairs_swept_through_other_returns_pair() {
+        let mut hash = SpatialHash::new(10.0);
+        hash.insert_swept(
+            0,
+            &Collider::new(Vector2::new(100.0, 0.0), 1.0),
+            Vector2::new(200.0, 0.0),
+        );
+        hash.insert(1, &Collider::new(Vector2::new(0.0, 0.0), 1.0));
+
+        let result = hash.candidate_pairs();
+
+        assert_eq!(result, vec![(0, 1)]);
+    }
+
+    #[test]
+    fn clear_removes_inserted_colliders() {
+        let mut hash = SpatialHash::new(10.0);
+        hash.insert(0, &Collider::new(Vector2::new(0.0, 0.0), 1.0));
+        hash.insert(1, &Collider::new(Vector2::new(0.0, 0.0), 1.0));
+
+        hash.clear();
+        let result = hash.candidate_pairs();
+
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn clear_drops_cells_no_longer_in_use() {
+        let mut hash = SpatialHash::new(10.0);
+        for x in 0..100 {
+            hash.insert(0, &Collider::new(Vector2::new(x as f64 * 10.0, 0.0), 1.0));
+            hash.clear();
+        }
+
+        hash.clear();
+
+        assert!(hash.cells.is_empty());
+    }
+
+    #[test]
+    fn colliding_pairs_randomized_scenes_match_brute_force() {
+        let mut random = Random(0x2545_f491_4f6c_dd1d);
+        for &cell_size in &[8.0, 25.0, 64.0, 500.0] {
+            for _ in 0..20 {
+                let scene = random_scene(&mut random, 60);
+                let colliders: Vec<&Collider> = scene.iter().collect();
+                let mut hash = SpatialHash::new(cell_size);
+                for (id, collider) in colliders.iter().enumerate() {
+                    hash.insert(id, collider);
+                }
+
+                let result = hash.colliding_pairs(&colliders);
+
+                assert_eq!(result, brute_force_pairs(&colliders));
+            }
+        }
+    }
+}
@@ -1,60 +1,100 @@
-use crate::collider::Collider;
-use crate::collides::Collides;
-use crate::position::Position;
+use serde::Deserialize;
+
+use crate::ai::{Brain, Personality};
+use crate::navigation::Route;
 use crate::settings;
-use crate::vector2::Vector2;
-use opengl_graphics::GlGraphics;
-use piston_window::{ellipse, Context};
+use crate::steering::{Behaviour, Wander};
 
-pub struct Chaser {
-    pub collider: Collider,
+/// Mix of steering behaviours a chaser is built with.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChaserKind {
+    /// Runs straight at the player.
+    Brute,
+    /// Cuts the player off by aiming where they are heading.
+    Hunter,
+    /// Meanders about while drifting towards the player.
+    Drifter,
 }
 
-impl Collides for Chaser {
-    fn get_collider(&mut self) -> &mut Collider {
-        &mut self.collider
-    }
+/// Steers the entity after the nearest player, in whatever way its brain
+/// is in the mood for.
+pub struct Chaser {
+    /// Top speed, the body's drag decides how quickly it gets there.
+    pub speed: f64,
+    pub brain: Brain,
+    /// Steering behaviours for each state the brain can be in. Stunned
+    /// chasers don't steer at all.
+    pub idle: Vec<(Behaviour, f64)>,
+    pub patrol: Vec<(Behaviour, f64)>,
+    pub chase: Vec<(Behaviour, f64)>,
+    pub flee: Vec<(Behaviour, f64)>,
+    /// Path around the walls to a player out of sight.
+    pub route: Route,
 }
 
-impl Position for Chaser {
-    fn set_position(&mut self, position: Vector2) {
-        self.get_collider().set_position(position)
-    }
-
-    fn get_position(&mut self) -> Vector2 {
-        self.get_collider().get_position()
+impl Default for Chaser {
+    fn default() -> Self {
+        Chaser::new()
     }
 }
 
 impl Chaser {
-    pub(crate) fn new(position: Vector2) -> Self {
-        Chaser {
-            collider: Collider::new(position, settings::chaser::SIZE),
-        }
+    pub fn new() -> Self {
+        Chaser::of_kind(ChaserKind::Brute, settings::chaser::SPEED, 0)
     }
 
-    pub fn update(&mut self, dt: f64, target_position: Vector2) {
-        let position = self.get_position();
-        if position == target_position {
-            return;
+    /// Chaser of the given kind. `seed` decides how it wanders.
+    pub fn of_kind(kind: ChaserKind, speed: f64, seed: u64) -> Self {
+        let separation = (
+            Behaviour::Separation {
+                radius: settings::steering::SEPARATION_RADIUS,
+            },
+            settings::steering::SEPARATION_WEIGHT,
+        );
+        let (personality, chase) = match kind {
+            ChaserKind::Brute => (
+                Personality::relentless(),
+                vec![(Behaviour::Seek, 1.0), separation.clone()],
+            ),
+            ChaserKind::Hunter => (
+                Personality::stalker(),
+                vec![
+                    (
+                        Behaviour::Pursue {
+                            max_prediction: settings::steering::MAX_PREDICTION,
+                        },
+                        1.0,
+                    ),
+                    separation.clone(),
+                ],
+            ),
+            ChaserKind::Drifter => (
+                Personality::skittish(),
+                vec![
+                    (Behaviour::Wander(Wander::new(seed)), 0.75),
+                    (
+                        Behaviour::Arrive {
+                            slowing_radius: settings::steering::SLOWING_RADIUS,
+                        },
+                        0.5,
+                    ),
+                    separation.clone(),
+                ],
+            ),
+        };
+        Chaser {
+            speed,
+            brain: Brain::new(personality),
+            idle: vec![separation.clone()],
+            // Patrols at a stroll.
+            patrol: vec![
+                (Behaviour::Wander(Wander::new(seed)), 0.5),
+                separation.clone(),
+            ],
+            chase,
+            flee: vec![(Behaviour::Flee, 1.0), separation],
+            route: Route::new(),
         }
-
-        self.set_position(Vector2::move_towards(
-            position,
-            target_position,
-            settings::chaser::SPEED * dt,
-        ));
-    }
-
-    pub fn draw(&mut self, c: Context, g: &mut GlGraphics) {
-        let position = self.get_position();
-        let rect = [
-            position.x - settings::chaser::SIZE,
-            position.y - settings::chaser::SIZE,
-            settings::chaser::SIZE * 2.0,
-            settings::chaser::SIZE * 2.0,
-        ];
-
-        ellipse(settings::color::RED, rect, c.transform, g);
     }
 }
@@ -1,7 +1,11 @@
+use crate::aabb::Aabb;
+use crate::layer;
+use crate::manifold::Manifold;
+use crate::ray::{Ray, RayHit};
+use crate::sat;
 use crate::settings;
+use crate::sweep;
 use crate::vector2::Vector2;
-use opengl_graphics::GlGraphics;
-use piston_window::ellipse;
 
 #[derive(Copy, Clone)]
 enum ColliderState {
@@ -9,10 +13,44 @@ enum ColliderState {
     Disabled,
 }
 
+/// Solid colliders get pushed out of each other, triggers only report that
+/// something overlaps them.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ColliderKind {
+    Solid,
+    Trigger,
+}
+
+/// Collider geometry relative to the collider's position.
+#[derive(Clone, Debug)]
+pub enum Shape {
+    Circle {
+        radius: f64,
+    },
+    /// Convex polygon, vertices in counter-clockwise order around the origin.
+    Polygon {
+        vertices: Vec<Vector2>,
+    },
+}
+
+/// How close two colliders are, relative to a margin around them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Proximity {
+    Touching,
+    /// Within the margin without touching, with the gap between them.
+    Near(f64),
+    Clear,
+}
+
+#[derive(Clone)]
 pub struct Collider {
     state: ColliderState,
+    kind: ColliderKind,
+    layer: u32,
+    mask: u32,
     position: Vector2,
-    radius: f64,
+    rotation: f64,
+    shape: Shape,
 }
 
 impl Collider {
@@ -22,43 +60,330 @@ impl Collider {
         }
         Collider {
             state: ColliderState::Enabled,
+            kind: ColliderKind::Solid,
+            layer: layer::ALL,
+            mask: layer::ALL,
+            position,
+            rotation: 0.0,
+            shape: Shape::Circle { radius },
+        }
+    }
+
+    pub fn new_polygon(position: Vector2, vertices: Vec<Vector2>) -> Self {
+        if vertices.len() < 3 {
+            panic!("Polygon collider must have at least 3 vertices");
+        }
+        let edges: Vec<Vector2> = vertices
+            .iter()
+            .zip(vertices.iter().cycle().skip(1))
+            .map(|(&start, &end)| end - start)
+            .collect();
+        let convex = edges
+            .iter()
+            .zip(edges.iter().cycle().skip(1))
+            .all(|(&edge, &next)| edge.cross(next) > 0.0);
+        if !convex {
+            panic!("Polygon collider must be convex and counter-clockwise");
+        }
+        Collider {
+            state: ColliderState::Enabled,
+            kind: ColliderKind::Solid,
+            layer: layer::ALL,
+            mask: layer::ALL,
             position,
-            radius,
+            rotation: 0.0,
+            shape: Shape::Polygon { vertices },
         }
     }
 
-    pub(crate) fn collides_with(&self, other: &Collider) -> bool {
+    /// Oriented box centered on `position`, rotated by `rotation` radians.
+    pub fn new_box(position: Vector2, half_width: f64, half_height: f64, rotation: f64) -> Self {
+        if half_width <= 0.0 || half_height <= 0.0 {
+            panic!("Half extents of box collider must be greater than 0");
+        }
+        let vertices = vec![
+            Vector2::new(-half_width, -half_height),
+            Vector2::new(half_width, -half_height),
+            Vector2::new(half_width, half_height),
+            Vector2::new(-half_width, half_height),
+        ];
+        let mut collider = Collider::new_polygon(position, vertices);
+        collider.rotation = rotation;
+        collider
+    }
+
+    pub fn collides_with(&self, other: &Collider) -> bool {
+        self.contact(other).is_some()
+    }
+
+    /// Whether the layers and masks of both colliders let them interact.
+    pub fn interacts_with(&self, other: &Collider) -> bool {
+        layer::interacts(self.layer, self.mask, other.layer, other.mask)
+    }
+
+    /// Contact manifold with the normal pointing from `self` towards `other`,
+    /// `None` when the colliders don't overlap, either one is disabled or
+    /// their layers don't interact.
+    pub fn contact(&self, other: &Collider) -> Option<Manifold> {
+        if !self.interacts_with(other) {
+            return None;
+        }
         match (self.state, other.state) {
-            (ColliderState::Enabled, ColliderState::Enabled) => {
-                let min_distance = self.radius + other.radius;
-                let distance = (self.position - other.position).magnitude();
-                distance < min_distance
+            (ColliderState::Enabled, ColliderState::Enabled) => match (&self.shape, &other.shape) {
+                (
+                    Shape::Circle { radius },
+                    Shape::Circle {
+                        radius: other_radius,
+                    },
+                ) => sat::circles_contact(self.position, *radius, other.position, *other_radius),
+                (Shape::Circle { radius }, Shape::Polygon { .. }) => {
+                    sat::circle_polygon_contact(self.position, *radius, &other.world_vertices())
+                }
+                (Shape::Polygon { .. }, Shape::Circle { radius }) => {
+                    sat::circle_polygon_contact(other.position, *radius, &self.world_vertices())
+                        .map(Manifold::flipped)
+                }
+                (Shape::Polygon { .. }, Shape::Polygon { .. }) => {
+                    sat::polygons_contact(&self.world_vertices(), &other.world_vertices())
+                }
+            },
+            (ColliderState::Disabled, _) => None,
+            (_, ColliderState::Disabled) => None,
+        }
+    }
+
+    /// Gap between the surfaces of the two colliders, `0.0` when they touch
+    /// or overlap. Purely geometric, so layers and disabled colliders are
+    /// measured like any other.
+    pub fn distance(&self, other: &Collider) -> f64 {
+        match (&self.shape, &other.shape) {
+            (
+                Shape::Circle { radius },
+                Shape::Circle {
+                    radius: other_radius,
+                },
+            ) => ((other.position - self.position).magnitude() - radius - other_radius).max(0.0),
+            (Shape::Circle { radius }, Shape::Polygon { .. }) => {
+                sat::circle_polygon_distance(self.position, *radius, &other.world_vertices())
+            }
+            (Shape::Polygon { .. }, Shape::Circle { radius }) => {
+                sat::circle_polygon_distance(other.position, *radius, &self.world_vertices())
+            }
+            (Shape::Polygon { .. }, Shape::Polygon { .. }) => {
+                sat::polygons_distance(&self.world_vertices(), &other.world_vertices())
+            }
+        }
+    }
+
+    /// Whether `other` touches `self`, comes within `margin` of it or stays
+    /// clear. Purely geometric like `distance`.
+    pub fn proximity(&self, other: &Collider, margin: f64) -> Proximity {
+        let distance = self.distance(other);
+        if distance <= 0.0 {
+            Proximity::Touching
+        } else if distance < margin {
+            Proximity::Near(distance)
+        } else {
+            Proximity::Clear
+        }
+    }
+
+    /// Fraction of the motion at which `self`, moving by `motion`, first
+    /// touches `other` moving by `other_motion`, `None` if they never meet
+    /// this frame. Both colliders are at their start positions.
+    pub fn time_of_impact(
+        &self,
+        motion: Vector2,
+        other: &Collider,
+        other_motion: Vector2,
+    ) -> Option<f64> {
+        if !self.interacts_with(other) {
+            return None;
+        }
+        match (self.state, other.state, &self.shape, &other.shape) {
+            (ColliderState::Disabled, ..) | (_, ColliderState::Disabled, ..) => None,
+            (
+                ..,
+                Shape::Circle { radius },
+                Shape::Circle {
+                    radius: other_radius,
+                },
+            ) => sweep::circles_time_of_impact(
+                self.position - other.position,
+                motion - other_motion,
+                radius + other_radius,
+            ),
+            _ => {
+                let relative = motion - other_motion;
+                let (start, end) = self.sweep_window(relative, other)?;
+                // Step no further than half the smaller collider per sample
+                // through the part of the motion where they can touch.
+                let size = self.min_extent().min(other.min_extent());
+                let distance = relative.magnitude() * (end - start);
+                let steps = (distance / (size / 2.0))
+                    .ceil()
+                    .min(settings::collision::MAX_SWEEP_STEPS as f64)
+                    as u32;
+                let mut moved = self.clone();
+                let mut other_moved = other.clone();
+                let at = |s: f64| start + (end - start) * s;
+                let s = sweep::sampled_time_of_impact(steps, |s| {
+                    moved.position = self.position + motion * at(s);
+                    other_moved.position = other.position + other_motion * at(s);
+                    moved.collides_with(&other_moved)
+                })?;
+                Some(at(s))
+            }
+        }
+    }
+
+    /// Part of `[0, 1]` during which `self`, moving by `motion` relative to
+    /// `other`, can touch it. Both are swept along the world axes, across
+    /// the motion and along every edge normal, and the contact has to lie
+    /// where their projections overlap on all of them.
+    fn sweep_window(&self, motion: Vector2, other: &Collider) -> Option<(f64, f64)> {
+        let mut axes = vec![Vector2::new(1.0, 0.0), Vector2::new(0.0, 1.0)];
+        if motion.magnitude() > 0.0 {
+            axes.push(motion.normalized().perpendicular());
+        }
+        axes.extend(sat::axes(&self.world_vertices()));
+        axes.extend(sat::axes(&other.world_vertices()));
+        axes.iter()
+            .try_fold((0.0, 1.0), |(start, end): (f64, f64), &axis| {
+                let (enter, exit) = sweep::projection_window(
+                    self.project(axis),
+                    other.project(axis),
+                    motion.dot(axis),
+                )?;
+                let window = (start.max(enter), end.min(exit));
+                (window.0 <= window.1).then_some(window)
+            })
+    }
+
+    /// Smallest and largest position of the collider along the unit `axis`.
+    fn project(&self, axis: Vector2) -> (f64, f64) {
+        match &self.shape {
+            Shape::Circle { radius } => {
+                let center = self.position.dot(axis);
+                (center - radius, center + radius)
+            }
+            Shape::Polygon { .. } => sat::project_polygon(&self.world_vertices(), axis),
+        }
+    }
+
+    /// Where the ray first enters the collider. Rays starting inside a
+    /// collider, or whose mask leaves out its layer, don't hit it. Line of
+    /// sight and path checks count on the first rule, so something resting
+    /// against or slightly sunk into a wall can still see away from it.
+    pub fn raycast(&self, ray: &Ray) -> Option<RayHit> {
+        if let ColliderState::Disabled = self.state {
+            return None;
+        }
+        if ray.mask & self.layer == 0 {
+            return None;
+        }
+        self.bounds().raycast(ray)?;
+        match &self.shape {
+            Shape::Circle { radius } => {
+                let offset = ray.origin - self.position;
+                let c = offset.dot(offset) - radius * radius;
+                let b = offset.dot(ray.direction);
+                if c <= 0.0 || b >= 0.0 {
+                    return None;
+                }
+                let discriminant = b * b - c;
+                if discriminant < 0.0 {
+                    return None;
+                }
+                let distance = -b - discriminant.sqrt();
+                if distance > ray.max_distance {
+                    return None;
+                }
+                let point = ray.point_at(distance);
+                Some(RayHit {
+                    distance,
+                    normal: (point - self.position).normalized(),
+                    point,
+                })
+            }
+            Shape::Polygon { .. } => {
+                // Clip the ray against every edge's half-plane (Cyrus-Beck).
+                let vertices = self.world_vertices();
+                let mut enter = 0.0;
+                let mut exit = ray.max_distance;
+                let mut enter_normal = None;
+                for (&start, &end) in vertices.iter().zip(vertices.iter().cycle().skip(1)) {
+                    let outward = -(end - start).perpendicular().normalized();
+                    let distance_outside = (ray.origin - start).dot(outward);
+                    let approach = ray.direction.dot(outward);
+                    if approach == 0.0 {
+                        if distance_outside > 0.0 {
+                            return None;
+                        }
+                        continue;
+                    }
+                    let t = -distance_outside / approach;
+                    if approach < 0.0 {
+                        if t > enter {
+                            enter = t;
+                            enter_normal = Some(outward);
+                        }
+                    } else {
+                        exit = f64::min(exit, t);
+                    }
+                    if enter > exit {
+                        return None;
+                    }
+                }
+                enter_normal.map(|normal| RayHit {
+                    distance: enter,
+                    normal,
+                    point: ray.point_at(enter),
+                })
+            }
+        }
+    }
+
+    /// Half of the collider's smallest bounding box side.
+    pub fn min_extent(&self) -> f64 {
+        let bounds = self.bounds();
+        let size = bounds.max - bounds.min;
+        size.x.min(size.y) / 2.0
+    }
+
+    pub fn bounds(&self) -> Aabb {
+        match &self.shape {
+            Shape::Circle { radius } => {
+                let extent = Vector2::new(*radius, *radius);
+                Aabb::new(self.position - extent, self.position + extent)
             }
-            (ColliderState::Disabled, _) => false,
-            (_, ColliderState::Disabled) => false,
+            Shape::Polygon { .. } => Aabb::from_points(&self.world_vertices()),
         }
     }
 
-    fn disable(&mut self) {
+    /// Polygon vertices rotated and moved into world space, empty for circles.
+    pub fn world_vertices(&self) -> Vec<Vector2> {
+        match &self.shape {
+            Shape::Circle { .. } => Vec::new(),
+            Shape::Polygon { vertices } => vertices
+                .iter()
+                .map(|vertex| vertex.rotated(self.rotation) + self.position)
+                .collect(),
+        }
+    }
+
+    pub fn disable(&mut self) {
         self.state = ColliderState::Disabled
     }
 
-    fn enable(&mut self) {
+    pub fn enable(&mut self) {
         self.state = ColliderState::Enabled
     }
 
-    pub fn draw_debug(&self, c: piston_window::Context, g: &mut GlGraphics) {
+    pub fn is_enabled(&self) -> bool {
         match self.state {
-            ColliderState::Enabled => {
-                let rect = [
-                    self.position.x - self.radius,
-                    self.position.y - self.radius,
-                    self.radius * 2.0,
-                    self.radius * 2.0,
-                ];
-                ellipse(settings::color::DEBUG, rect, c.transform, g);
-            }
-            ColliderState::Disabled => {}
+            ColliderState::Enabled => true,
+            ColliderState::Disabled => false,
         }
     }
 
@@ -69,12 +394,65 @@ impl Collider {
     pub fn set_position(&mut self, position: Vector2) {
         self.position = position
     }
+
+    pub fn get_kind(&self) -> ColliderKind {
+        self.kind
+    }
+
+    pub fn set_kind(&mut self, kind: ColliderKind) {
+        self.kind = kind
+    }
+
+    pub fn is_trigger(&self) -> bool {
+        self.kind == ColliderKind::Trigger
+    }
+
+    pub fn get_layer(&self) -> u32 {
+        self.layer
+    }
+
+    pub fn set_layer(&mut self, layer: u32) {
+        self.layer = layer
+    }
+
+    pub fn get_mask(&self) -> u32 {
+        self.mask
+    }
+
+    pub fn set_mask(&mut self, mask: u32) {
+        self.mask = mask
+    }
+
+    pub fn get_rotation(&self) -> f64 {
+        self.rotation
+    }
+
+    pub fn set_rotation(&mut self, rotation: f64) {
+        self.rotation = rotation
+    }
+
+    pub fn get_shape(&self) -> &Shape {
+        &self.shape
+    }
+
+    /// Resizes a circle, polygons keep their size.
+    pub fn set_radius(&mut self, radius: f64) {
+        if radius <= 0.0 {
+            panic!("Radius of collider must be greater than 0");
+        }
+        if let Shape::Circle { radius: current } = &mut self.shape {
+            *current = radius;
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::collider::Collider;
+    use crate::collider::{Collider, ColliderKind, Proximity};
+    use crate::layer;
+    use crate::ray::Ray;
     use crate::vector2::Vector2;
+    use std::f64::consts::FRAC_PI_4;
 
     #[test]
     #[should_panic]
@@ -84,6 +462,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn collides_with_not_colliding_with_other_returns_false() {
         let collider = Collider::new(Vector2::new(0.0, 0.0), 1.0);
         let other = Collider::new(Vector2::new(10.0, 10.0), 1.0);
@@ -94,6 +473,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn collides_with_colliding_with_other_returns_true() {
         let collider = Collider::new(Vector2::new(0.0, 0.0), 1.0);
         let other = Collider::new(Vector2::new(0.0, 0.0), 1.0);
@@ -104,6 +484,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn collides_with_colliding_with_disabled_other_returns_false() {
         let collider = Collider::new(Vector2::new(0.0, 0.0), 1.0);
         let mut other = Collider::new(Vector2::new(0.0, 0.0), 1.0);
@@ -115,6 +496,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn collides_with_colliding_with_enabled_other_returns_true() {
         let collider = Collider::new(Vector2::new(0.0, 0.0), 1.0);
         let mut other = Collider::new(Vector2::new(0.0, 0.0), 1.0);
@@ -125,4 +507,408 @@ mod tests {
 
         assert_eq!(result, true)
     }
+
+    #[test]
+    #[should_panic]
+    fn new_polygon_less_than_three_vertices_panic() {
+        let vertices = vec![Vector2::new(0.0, 0.0), Vector2::new(1.0, 0.0)];
+        Collider::new_polygon(Vector2::new(0.0, 0.0), vertices);
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_polygon_concave_vertices_panic() {
+        let vertices = vec![
+            Vector2::new(0.0, 0.0),
+            Vector2::new(2.0, 0.0),
+            Vector2::new(1.0, 0.5),
+            Vector2::new(2.0, 2.0),
+            Vector2::new(0.0, 2.0),
+        ];
+        Collider::new_polygon(Vector2::new(0.0, 0.0), vertices);
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_box_half_extent_less_than_equal_zero_panic() {
+        Collider::new_box(Vector2::new(0.0, 0.0), 1.0, 0.0, 0.0);
+    }
+
+    #[test]
+    fn world_vertices_rotated_box_returns_rotated_corners() {
+        let collider = Collider::new_box(Vector2::new(10.0, 0.0), 1.0, 1.0, FRAC_PI_4);
+
+        let result = collider.world_vertices();
+
+        let corner = (2.0_f64).sqrt();
+        assert!((result[0] - Vector2::new(10.0, -corner)).magnitude() < 1e-12);
+        assert!((result[1] - Vector2::new(10.0 + corner, 0.0)).magnitude() < 1e-12);
+    }
+
+    #[test]
+    fn distance_separated_circles_returns_gap_between_surfaces() {
+        let collider = Collider::new(Vector2::new(0.0, 0.0), 1.0);
+        let other = Collider::new(Vector2::new(0.0, 10.0), 2.0);
+
+        assert_eq!(collider.distance(&other), 7.0);
+        assert_eq!(other.distance(&collider), 7.0);
+    }
+
+    #[test]
+    fn distance_overlapping_circle_and_box_returns_zero() {
+        let collider = Collider::new(Vector2::new(1.5, 0.0), 1.0);
+        let other = Collider::new_box(Vector2::new(0.0, 0.0), 1.0, 1.0, 0.0);
+
+        assert_eq!(collider.distance(&other), 0.0);
+    }
+
+    #[test]
+    fn proximity_within_margin_returns_near_with_gap() {
+        let collider = Collider::new(Vector2::new(0.0, 0.0), 1.0);
+        let near = Collider::new(Vector2::new(4.0, 0.0), 1.0);
+        let far = Collider::new(Vector2::new(10.0, 0.0), 1.0);
+        let touching = Collider::new(Vector2::new(1.0, 0.0), 1.0);
+
+        assert_eq!(collider.proximity(&near, 5.0), Proximity::Near(2.0));
+        assert_eq!(collider.proximity(&far, 5.0), Proximity::Clear);
+        assert_eq!(collider.proximity(&touching, 5.0), Proximity::Touching);
+    }
+
+    #[test]
+    fn collides_with_circle_overlapping_box_returns_true() {
+        let collider = Collider::new(Vector2::new(0.0, 0.0), 1.0);
+        let other = Collider::new_box(Vector2::new(1.5, 0.0), 1.0, 1.0, 0.0);
+
+        let result = collider.collides_with(&other);
+
+        assert!(result)
+    }
+
+    #[test]
+    fn collides_with_box_not_colliding_with_circle_returns_false() {
+        let collider = Collider::new_box(Vector2::new(0.0, 0.0), 1.0, 1.0, 0.0);
+        let other = Collider::new(Vector2::new(1.8, 1.8), 1.0);
+
+        let result = collider.collides_with(&other);
+
+        assert!(!result)
+    }
+
+    #[test]
+    fn collides_with_rotated_box_not_colliding_with_box_returns_false() {
+        // The axis-aligned bounds overlap, only the rotation keeps them apart.
+        let collider = Collider::new_box(Vector2::new(0.0, 0.0), 1.0, 1.0, FRAC_PI_4);
+        let other = Collider::new_box(Vector2::new(2.3, 2.3), 1.0, 1.0, 0.0);
+
+        let result = collider.collides_with(&other);
+
+        assert!(!result)
+    }
+
+    #[test]
+    fn collides_with_rotated_box_colliding_with_box_returns_true() {
+        let collider = Collider::new_box(Vector2::new(0.0, 0.0), 1.0, 1.0, FRAC_PI_4);
+        let other = Collider::new_box(Vector2::new(2.3, 0.0), 1.0, 1.0, 0.0);
+
+        let result = collider.collides_with(&other);
+
+        assert!(result)
+    }
+
+    #[test]
+    fn collides_with_box_colliding_with_disabled_polygon_returns_false() {
+        let collider = Collider::new_box(Vector2::new(0.0, 0.0), 1.0, 1.0, 0.0);
+        let mut other = Collider::new_box(Vector2::new(0.0, 0.0), 1.0, 1.0, 0.0);
+
+        other.disable();
+        let result = collider.collides_with(&other);
+
+        assert!(!result)
+    }
+
+    #[test]
+    fn contact_not_colliding_with_other_returns_none() {
+        let collider = Collider::new(Vector2::new(0.0, 0.0), 1.0);
+        let other = Collider::new(Vector2::new(10.0, 10.0), 1.0);
+
+        let result = collider.contact(&other);
+
+        assert!(result.is_none())
+    }
+
+    #[test]
+    fn contact_colliding_with_disabled_other_returns_none() {
+        let collider = Collider::new(Vector2::new(0.0, 0.0), 1.0);
+        let mut other = Collider::new(Vector2::new(0.0, 0.0), 1.0);
+
+        other.disable();
+        let result = collider.contact(&other);
+
+        assert!(result.is_none())
+    }
+
+    #[test]
+    fn contact_box_colliding_with_circle_normal_points_towards_circle() {
+        let collider = Collider::new_box(Vector2::new(0.0, 0.0), 1.0, 1.0, 0.0);
+        let other = Collider::new(Vector2::new(0.0, 1.5), 1.0);
+
+        let result = collider.contact(&other).unwrap();
+
+        assert_eq!(result.normal, Vector2::new(0.0, 1.0));
+        assert_eq!(result.depth, 0.5);
+    }
+
+    #[test]
+    fn contact_minimum_translation_separates_colliders() {
+        let mut collider = Collider::new(Vector2::new(0.0, 0.0), 1.0);
+        let other = Collider::new_box(Vector2::new(1.5, 0.2), 1.0, 1.0, 0.3);
+
+        let manifold = collider.contact(&other).unwrap();
+        let position = collider.get_position() + manifold.minimum_translation() * 1.001;
+        collider.set_position(position);
+
+        assert!(!collider.collides_with(&other))
+    }
+
+    #[test]
+    fn bounds_circle_returns_enclosing_square() {
+        let collider = Collider::new(Vector2::new(1.0, 2.0), 3.0);
+
+        let result = collider.bounds();
+
+        assert_eq!(result.min, Vector2::new(-2.0, -1.0));
+        assert_eq!(result.max, Vector2::new(4.0, 5.0));
+    }
+
+    #[test]
+    fn bounds_rotated_box_encloses_corners() {
+        let collider = Collider::new_box(Vector2::new(0.0, 0.0), 1.0, 1.0, FRAC_PI_4);
+
+        let result = collider.bounds();
+
+        let corner = (2.0_f64).sqrt();
+        assert!((result.max - Vector2::new(corner, corner)).magnitude() < 1e-12);
+        assert!((result.min + Vector2::new(corner, corner)).magnitude() < 1e-12);
+    }
+
+    #[test]
+    fn collides_with_other_outside_mask_returns_false() {
+        let mut collider = Collider::new(Vector2::new(0.0, 0.0), 1.0);
+        let mut other = Collider::new(Vector2::new(0.0, 0.0), 1.0);
+
+        collider.set_layer(layer::ENEMY);
+        collider.set_mask(layer::PLAYER);
+        other.set_layer(layer::ENEMY);
+        other.set_mask(layer::PLAYER);
+        let result = collider.collides_with(&other);
+
+        assert!(!result)
+    }
+
+    #[test]
+    fn collides_with_other_masking_out_self_returns_false() {
+        let mut collider = Collider::new(Vector2::new(0.0, 0.0), 1.0);
+        let mut other = Collider::new(Vector2::new(0.0, 0.0), 1.0);
+
+        collider.set_layer(layer::PROJECTILE);
+        other.set_layer(layer::PICKUP);
+        other.set_mask(layer::PLAYER);
+        let result = collider.collides_with(&other);
+
+        assert!(!result)
+    }
+
+    #[test]
+    fn collides_with_other_in_mask_returns_true() {
+        let mut collider = Collider::new(Vector2::new(0.0, 0.0), 1.0);
+        let mut other = Collider::new(Vector2::new(0.0, 0.0), 1.0);
+
+        collider.set_layer(layer::ENEMY);
+        collider.set_mask(layer::PLAYER);
+        other.set_layer(layer::PLAYER);
+        other.set_mask(layer::ENEMY | layer::WALL);
+        let result = collider.collides_with(&other);
+
+        assert!(result)
+    }
+
+    #[test]
+    fn collides_with_trigger_overlapping_other_returns_true() {
+        let mut collider = Collider::new(Vector2::new(0.0, 0.0), 1.0);
+        let other = Collider::new(Vector2::new(0.5, 0.0), 1.0);
+
+        collider.set_kind(ColliderKind::Trigger);
+        let result = collider.collides_with(&other);
+
+        assert!(result)
+    }
+
+    #[test]
+    fn time_of_impact_fast_circle_through_circle_returns_contact_time() {
+        let collider = Collider::new(Vector2::new(-100.0, 0.0), 1.0);
+        let other = Collider::new(Vector2::new(0.0, 0.0), 1.0);
+
+        let result =
+            collider.time_of_impact(Vector2::new(200.0, 0.0), &other, Vector2::new(0.0, 0.0));
+
+        assert_eq!(result, Some(0.49));
+    }
+
+    #[test]
+    fn time_of_impact_circle_through_thin_box_returns_contact_time() {
+        let collider = Collider::new(Vector2::new(-100.0, 0.0), 1.0);
+        let wall = Collider::new_box(Vector2::new(0.0, 0.0), 0.5, 50.0, 0.0);
+
+        let result = collider
+            .time_of_impact(Vector2::new(200.0, 0.0), &wall, Vector2::new(0.0, 0.0))
+            .unwrap();
+
+        assert!((result - 0.4925).abs() < 1e-4);
+    }
+
+    #[test]
+    fn time_of_impact_both_moving_box_and_circle_returns_contact_time() {
+        let collider = Collider::new_box(Vector2::new(-10.0, 0.0), 1.0, 1.0, 0.0);
+        let other = Collider::new(Vector2::new(10.0, 0.0), 1.0);
+
+        let result = collider
+            .time_of_impact(Vector2::new(10.0, 0.0), &other, Vector2::new(-10.0, 0.0))
+            .unwrap();
+
+        assert!((result - 0.9).abs() < 1e-4);
+    }
+
+    #[test]
+    fn time_of_impact_missing_box_returns_none() {
+        let collider = Collider::new(Vector2::new(-100.0, 10.0), 1.0);
+        let wall = Collider::new_box(Vector2::new(0.0, 0.0), 0.5, 5.0, 0.0);
+
+        let result =
+            collider.time_of_impact(Vector2::new(200.0, 0.0), &wall, Vector2::new(0.0, 0.0));
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn time_of_impact_tiny_circle_huge_motion_still_hits_thick_box() {
+        let collider = Collider::new(Vector2::new(-5.0e8, 0.0), 1.0e-6);
+        let wall = Collider::new_box(Vector2::new(0.0, 0.0), 1.0e7, 1.0e7, 0.0);
+
+        let result = collider
+            .time_of_impact(Vector2::new(1.0e9, 0.0), &wall, Vector2::new(0.0, 0.0))
+            .unwrap();
+
+        assert!((result - 0.49).abs() < 1e-3);
+    }
+
+    #[test]
+    fn time_of_impact_long_motion_through_thin_wall_returns_contact_time() {
+        let collider = Collider::new(Vector2::new(-500.0, 0.0), 1.0);
+        let wall = Collider::new_box(Vector2::new(0.0, 0.0), 0.5, 50.0, 0.0);
+
+        let result = collider
+            .time_of_impact(Vector2::new(1000.0, 0.0), &wall, Vector2::new(0.0, 0.0))
+            .unwrap();
+
+        assert!((result - 0.4985).abs() < 1e-4);
+    }
+
+    #[test]
+    fn time_of_impact_disabled_other_returns_none() {
+        let collider = Collider::new(Vector2::new(-100.0, 0.0), 1.0);
+        let mut other = Collider::new(Vector2::new(0.0, 0.0), 1.0);
+
+        other.disable();
+        let result =
+            collider.time_of_impact(Vector2::new(200.0, 0.0), &other, Vector2::new(0.0, 0.0));
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn raycast_towards_circle_returns_surface_hit() {
+        let collider = Collider::new(Vector2::new(10.0, 0.0), 2.0);
+        let ray = Ray::new(Vector2::new(0.0, 0.0), Vector2::new(1.0, 0.0), 100.0);
+
+        let result = collider.raycast(&ray).unwrap();
+
+        assert_eq!(result.distance, 8.0);
+        assert_eq!(result.normal, Vector2::new(-1.0, 0.0));
+        assert_eq!(result.point, Vector2::new(8.0, 0.0));
+    }
+
+    #[test]
+    fn raycast_away_from_circle_returns_none() {
+        let collider = Collider::new(Vector2::new(10.0, 0.0), 2.0);
+        let ray = Ray::new(Vector2::new(0.0, 0.0), Vector2::new(-1.0, 0.0), 100.0);
+
+        let result = collider.raycast(&ray);
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn raycast_from_inside_circle_returns_none() {
+        let collider = Collider::new(Vector2::new(0.0, 0.0), 2.0);
+        let ray = Ray::new(Vector2::new(0.0, 0.0), Vector2::new(1.0, 0.0), 100.0);
+
+        let result = collider.raycast(&ray);
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn raycast_from_inside_box_returns_none() {
+        let collider = Collider::new_box(Vector2::new(0.0, 0.0), 10.0, 10.0, 0.0);
+        let ray = Ray::new(Vector2::new(9.0, 0.0), Vector2::new(-1.0, 0.0), 100.0);
+
+        let result = collider.raycast(&ray);
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn raycast_towards_rotated_box_hits_corner_face() {
+        let collider = Collider::new_box(Vector2::new(10.0, 0.0), 1.0, 1.0, FRAC_PI_4);
+        let ray = Ray::new(Vector2::new(0.0, 0.0), Vector2::new(1.0, 0.0), 100.0);
+
+        let result = collider.raycast(&ray).unwrap();
+
+        assert!((result.distance - (10.0 - (2.0_f64).sqrt())).abs() < 1e-12);
+        assert!(result.normal.x < 0.0);
+        assert!((result.normal.magnitude() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn raycast_towards_box_returns_face_normal() {
+        let collider = Collider::new_box(Vector2::new(0.0, 10.0), 5.0, 1.0, 0.0);
+        let ray = Ray::new(Vector2::new(2.0, 0.0), Vector2::new(0.0, 1.0), 100.0);
+
+        let result = collider.raycast(&ray).unwrap();
+
+        assert_eq!(result.distance, 9.0);
+        assert_eq!(result.normal, Vector2::new(0.0, -1.0));
+    }
+
+    #[test]
+    fn raycast_passing_beside_box_returns_none() {
+        let collider = Collider::new_box(Vector2::new(0.0, 10.0), 5.0, 1.0, 0.0);
+        let ray = Ray::new(Vector2::new(6.0, 0.0), Vector2::new(0.0, 1.0), 100.0);
+
+        let result = collider.raycast(&ray);
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn raycast_masked_out_layer_returns_none() {
+        let mut collider = Collider::new(Vector2::new(10.0, 0.0), 2.0);
+        let mut ray = Ray::new(Vector2::new(0.0, 0.0), Vector2::new(1.0, 0.0), 100.0);
+
+        collider.set_layer(layer::ENEMY);
+        ray.mask = layer::WALL;
+        let result = collider.raycast(&ray);
+
+        assert!(result.is_none());
+    }
 }
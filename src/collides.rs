@@ -1,9 +1,88 @@
 use crate::collider::Collider;
+use crate::manifold::Manifold;
 
 pub trait Collides {
     fn collides_with<C: Collides>(&mut self, other: &mut C) -> bool {
-        self.get_collider().collides_with(other.get_collider())
+        // Spelled out so `Collider`'s own impl doesn't call back into this.
+        Collider::collides_with(self.get_collider(), other.get_collider())
+    }
+
+    fn contact_with<C: Collides>(&mut self, other: &mut C) -> Option<Manifold> {
+        self.get_collider().contact(other.get_collider())
+    }
+
+    /// Pushes both colliders half of the penetration depth apart along the
+    /// contact normal and returns the contact they were resolved from.
+    /// Triggers are never moved, so nothing happens if either is one.
+    fn separate_from<C: Collides>(&mut self, other: &mut C) -> Option<Manifold> {
+        if self.get_collider().is_trigger() || other.get_collider().is_trigger() {
+            return None;
+        }
+        let manifold = self.contact_with(other)?;
+        let correction = manifold.minimum_translation() / 2.0;
+
+        let collider = self.get_collider();
+        collider.set_position(collider.get_position() + correction);
+        let other_collider = other.get_collider();
+        other_collider.set_position(other_collider.get_position() - correction);
+
+        Some(manifold)
     }
 
     fn get_collider(&mut self) -> &mut Collider;
 }
+
+impl Collides for Collider {
+    fn get_collider(&mut self) -> &mut Collider {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collider::{Collider, ColliderKind};
+    use crate::collides::Collides;
+    use crate::vector2::Vector2;
+
+    struct Body {
+        collider: Collider,
+    }
+
+    impl Collides for Body {
+        fn get_collider(&mut self) -> &mut Collider {
+            &mut self.collider
+        }
+    }
+
+    fn body(x: f64) -> Body {
+        Body {
+            collider: Collider::new(Vector2::new(x, 0.0), 1.0),
+        }
+    }
+
+    #[test]
+    fn separate_from_overlapping_other_moves_both_apart() {
+        let mut a = body(0.0);
+        let mut b = body(1.0);
+
+        let result = a.separate_from(&mut b);
+
+        assert!(result.is_some());
+        assert_eq!(a.collider.get_position(), Vector2::new(-0.5, 0.0));
+        assert_eq!(b.collider.get_position(), Vector2::new(1.5, 0.0));
+    }
+
+    #[test]
+    fn separate_from_trigger_leaves_both_in_place() {
+        let mut a = body(0.0);
+        let mut trigger = body(1.0);
+        trigger.collider.set_kind(ColliderKind::Trigger);
+
+        let result = a.separate_from(&mut trigger);
+
+        assert!(result.is_none());
+        assert!(a.collides_with(&mut trigger));
+        assert_eq!(a.collider.get_position(), Vector2::new(0.0, 0.0));
+        assert_eq!(trigger.collider.get_position(), Vector2::new(1.0, 0.0));
+    }
+}
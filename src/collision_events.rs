@@ -0,0 +1,151 @@
+use std::collections::BTreeSet;
+
+/// Change in contact between two colliders, identified by ids that stay the
+/// same from frame to frame, smaller id first.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CollisionEvent<T = usize> {
+    Enter(T, T),
+    Stay(T, T),
+    Exit(T, T),
+}
+
+/// Remembers which pairs touched last frame to turn per-frame overlaps into
+/// enter, stay and exit events.
+pub struct ContactTracker<T = usize> {
+    touching: BTreeSet<(T, T)>,
+}
+
+impl<T: Copy + Ord> Default for ContactTracker<T> {
+    fn default() -> Self {
+        ContactTracker::new()
+    }
+}
+
+impl<T: Copy + Ord> ContactTracker<T> {
+    pub fn new() -> Self {
+        ContactTracker {
+            touching: BTreeSet::new(),
+        }
+    }
+
+    /// Takes this frame's overlapping pairs and returns the events, exits
+    /// first, then enters and stays in pair order.
+    pub fn update(&mut self, pairs: &[(T, T)]) -> Vec<CollisionEvent<T>> {
+        let current: BTreeSet<(T, T)> = pairs.iter().map(|&(a, b)| (a.min(b), a.max(b))).collect();
+
+        let exits = self
+            .touching
+            .difference(&current)
+            .map(|&(a, b)| CollisionEvent::Exit(a, b));
+        let enters_and_stays = current.iter().map(|&(a, b)| {
+            if self.touching.contains(&(a, b)) {
+                CollisionEvent::Stay(a, b)
+            } else {
+                CollisionEvent::Enter(a, b)
+            }
+        });
+        let events = exits.chain(enters_and_stays).collect();
+
+        self.touching = current;
+        events
+    }
+
+    /// Forgets a collider that went away, returning exits for its contacts.
+    pub fn remove(&mut self, id: T) -> Vec<CollisionEvent<T>> {
+        let (removed, kept) = self
+            .touching
+            .iter()
+            .partition(|&&(a, b)| a == id || b == id);
+        self.touching = kept;
+        removed
+            .into_iter()
+            .map(|(a, b)| CollisionEvent::Exit(a, b))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collision_events::{CollisionEvent, ContactTracker};
+
+    #[test]
+    fn update_new_pair_returns_enter() {
+        let mut tracker: ContactTracker = ContactTracker::new();
+
+        let result = tracker.update(&[(0, 1)]);
+
+        assert_eq!(result, vec![CollisionEvent::Enter(0, 1)]);
+    }
+
+    #[test]
+    fn update_pair_still_touching_returns_stay() {
+        let mut tracker: ContactTracker = ContactTracker::new();
+
+        tracker.update(&[(0, 1)]);
+        let result = tracker.update(&[(0, 1)]);
+
+        assert_eq!(result, vec![CollisionEvent::Stay(0, 1)]);
+    }
+
+    #[test]
+    fn update_pair_no_longer_touching_returns_exit() {
+        let mut tracker: ContactTracker = ContactTracker::new();
+
+        tracker.update(&[(0, 1)]);
+        let result = tracker.update(&[]);
+
+        assert_eq!(result, vec![CollisionEvent::Exit(0, 1)]);
+    }
+
+    #[test]
+    fn update_exited_pair_returns_nothing_afterwards() {
+        let mut tracker: ContactTracker = ContactTracker::new();
+
+        tracker.update(&[(0, 1)]);
+        tracker.update(&[]);
+        let result = tracker.update(&[]);
+
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn update_reversed_pair_is_same_contact() {
+        let mut tracker: ContactTracker = ContactTracker::new();
+
+        tracker.update(&[(1, 0)]);
+        let result = tracker.update(&[(0, 1)]);
+
+        assert_eq!(result, vec![CollisionEvent::Stay(0, 1)]);
+    }
+
+    #[test]
+    fn update_mixed_pairs_returns_exits_before_enters_and_stays() {
+        let mut tracker: ContactTracker = ContactTracker::new();
+
+        tracker.update(&[(0, 1), (2, 3)]);
+        let result = tracker.update(&[(2, 3), (0, 4)]);
+
+        assert_eq!(
+            result,
+            vec![
+                CollisionEvent::Exit(0, 1),
+                CollisionEvent::Enter(0, 4),
+                CollisionEvent::Stay(2, 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn remove_touching_collider_returns_exits() {
+        let mut tracker: ContactTracker = ContactTracker::new();
+
+        tracker.update(&[(0, 1), (1, 2), (2, 3)]);
+        let result = tracker.remove(1);
+
+        assert_eq!(
+            result,
+            vec![CollisionEvent::Exit(0, 1), CollisionEvent::Exit(1, 2)]
+        );
+        assert_eq!(tracker.update(&[(2, 3)]), vec![CollisionEvent::Stay(2, 3)]);
+    }
+}
@@ -0,0 +1,146 @@
+//! Plain data components that don't belong to a more specific module.
+
+/// How an entity is drawn.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sprite {
+    pub color: [f32; 4],
+    pub radius: f64,
+}
+
+impl Sprite {
+    pub fn new(color: [f32; 4], radius: f64) -> Self {
+        Sprite { color, radius }
+    }
+}
+
+/// Hit points, optionally with a spell of invulnerability after each hit
+/// so one touch doesn't drain them all at once.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Health {
+    current: u32,
+    max: u32,
+    invulnerable_time: f64,
+    invulnerable_for: f64,
+}
+
+impl Health {
+    pub fn new(max: u32) -> Self {
+        Health::with_invulnerability(max, 0.0)
+    }
+
+    /// Health that ignores damage for `invulnerable_time` seconds after
+    /// every hit.
+    pub fn with_invulnerability(max: u32, invulnerable_time: f64) -> Self {
+        if max == 0 {
+            panic!("Maximum health must be greater than 0");
+        }
+        Health {
+            current: max,
+            max,
+            invulnerable_time,
+            invulnerable_for: 0.0,
+        }
+    }
+
+    pub fn get_current(&self) -> u32 {
+        self.current
+    }
+
+    pub fn get_max(&self) -> u32 {
+        self.max
+    }
+
+    /// Takes `amount` off unless invulnerable, returns whether it did.
+    pub fn damage(&mut self, amount: u32) -> bool {
+        if self.is_invulnerable() || self.is_depleted() {
+            return false;
+        }
+        self.current = self.current.saturating_sub(amount);
+        self.invulnerable_for = self.invulnerable_time;
+        true
+    }
+
+    /// Back to full health, invulnerable for a while as if just hit.
+    pub fn restore(&mut self) {
+        self.current = self.max;
+        self.invulnerable_for = self.invulnerable_time;
+    }
+
+    /// Counts down the invulnerability.
+    pub fn update(&mut self, dt: f64) {
+        self.invulnerable_for = (self.invulnerable_for - dt).max(0.0);
+    }
+
+    pub fn is_invulnerable(&self) -> bool {
+        self.invulnerable_for > 0.0
+    }
+
+    pub fn is_depleted(&self) -> bool {
+        self.current == 0
+    }
+}
+
+/// Health taken from whatever the entity touches.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ContactDamage {
+    pub amount: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::Health;
+
+    #[test]
+    #[should_panic]
+    fn new_health_max_zero_panic() {
+        Health::new(0);
+    }
+
+    #[test]
+    fn damage_lowers_current_health() {
+        let mut health = Health::new(3);
+
+        health.damage(1);
+
+        assert_eq!(health.get_current(), 2);
+        assert_eq!(health.get_max(), 3);
+        assert!(!health.is_depleted());
+    }
+
+    #[test]
+    fn damage_while_invulnerable_is_ignored_until_it_wears_off() {
+        let mut health = Health::with_invulnerability(3, 1.0);
+
+        let first = health.damage(1);
+        let second = health.damage(1);
+        health.update(1.0);
+        let third = health.damage(1);
+
+        assert!(first);
+        assert!(!second);
+        assert!(third);
+        assert_eq!(health.get_current(), 1);
+    }
+
+    #[test]
+    fn restore_refills_and_grants_invulnerability() {
+        let mut health = Health::with_invulnerability(3, 1.0);
+        health.damage(2);
+        health.update(1.0);
+
+        health.restore();
+
+        assert_eq!(health.get_current(), 3);
+        assert!(health.is_invulnerable());
+    }
+
+    #[test]
+    fn damage_more_than_current_stops_at_zero() {
+        let mut health = Health::new(3);
+
+        health.damage(5);
+
+        assert_eq!(health.get_current(), 0);
+        assert!(health.is_depleted());
+    }
+}
@@ -0,0 +1,794 @@
+//! Gameplay settings read from a TOML file at startup, and again whenever
+//! the file changes while the game runs.
+//!
+//! Every value has a default taken from the constants in `settings`, so the
+//! file only needs the ones that differ, section by section:
+//!
+//! ```toml
+//! [player]
+//! speed = 300.0
+//!
+//! [waves]
+//! kinds = ["hunter", "drifter"]
+//! ```
+//!
+//! Values given on the command line as `--set player.speed=300` win over
+//! the file. Whatever the source, the settings are checked before use and
+//! anything out of range is reported as an error rather than left to panic
+//! deep inside the game. A file that goes bad while it is being watched
+//! leaves the last good settings in place.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::chaser::ChaserKind;
+use crate::settings;
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(PathBuf, io::Error),
+    Parse(toml::de::Error),
+    /// A value that parsed but is out of range.
+    Invalid(String),
+    /// Command line arguments that don't make sense.
+    Usage(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Io(path, error) => write!(f, "{}: {}", path.display(), error),
+            ConfigError::Parse(error) => write!(f, "{}", error),
+            ConfigError::Invalid(message) | ConfigError::Usage(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+impl From<toml::de::Error> for ConfigError {
+    fn from(error: toml::de::Error) -> Self {
+        ConfigError::Parse(error)
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    pub window: WindowSettings,
+    pub color: ColorSettings,
+    pub player: PlayerSettings,
+    pub chaser: ChaserSettings,
+    pub waves: WaveSettings,
+    pub score: ScoreSettings,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct WindowSettings {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Default for WindowSettings {
+    fn default() -> Self {
+        let (width, height) = settings::window::SIZE;
+        WindowSettings { width, height }
+    }
+}
+
+/// Colours as red, green, blue and alpha from `0.0` to `1.0`.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ColorSettings {
+    pub background: [f32; 4],
+    pub player: [f32; 4],
+    pub chaser: [f32; 4],
+    pub obstacle: [f32; 4],
+    pub text: [f32; 4],
+    /// Tint over the world while paused.
+    pub paused: [f32; 4],
+    /// Tint over the world once the game is over.
+    pub game_over: [f32; 4],
+}
+
+impl Default for ColorSettings {
+    fn default() -> Self {
+        use settings::color;
+        ColorSettings {
+            background: color::GREY,
+            player: color::WHITE,
+            chaser: color::RED,
+            obstacle: color::OBSTACLE,
+            text: color::TEXT,
+            paused: color::PAUSED,
+            game_over: color::GAME_OVER,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlayerSettings {
+    pub size: f64,
+    pub speed: f64,
+    pub acceleration: f64,
+    pub mass: f64,
+    pub restitution: f64,
+    pub health: u32,
+    pub lives: u32,
+    pub invulnerable_time: f64,
+    pub respawn_time: f64,
+}
+
+impl PlayerSettings {
+    /// Drag that makes `speed` the top speed under full acceleration.
+    pub fn drag(&self) -> f64 {
+        self.acceleration / self.speed
+    }
+}
+
+impl Default for PlayerSettings {
+    fn default() -> Self {
+        use settings::player;
+        PlayerSettings {
+            size: player::SIZE,
+            speed: player::SPEED,
+            acceleration: player::ACCELERATION,
+            mass: player::MASS,
+            restitution: player::RESTITUTION,
+            health: player::HEALTH,
+            lives: player::LIVES,
+            invulnerable_time: player::INVULNERABLE_TIME,
+            respawn_time: player::RESPAWN_TIME,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ChaserSettings {
+    /// Size and speed of chasers in the first wave, and of chasers spawned
+    /// outside of waves.
+    pub size: f64,
+    pub speed: f64,
+    pub acceleration: f64,
+    pub mass: f64,
+    pub restitution: f64,
+    pub damage: u32,
+}
+
+impl ChaserSettings {
+    /// Drag that makes `speed` the top speed under full acceleration.
+    pub fn drag(&self) -> f64 {
+        self.acceleration / self.speed
+    }
+}
+
+impl Default for ChaserSettings {
+    fn default() -> Self {
+        use settings::chaser;
+        ChaserSettings {
+            size: chaser::SIZE,
+            speed: chaser::SPEED,
+            acceleration: chaser::ACCELERATION,
+            mass: chaser::MASS,
+            restitution: chaser::RESTITUTION,
+            damage: chaser::DAMAGE,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct WaveSettings {
+    pub interval: f64,
+    pub stagger: f64,
+    /// Spawn points along every edge, from `0.0` to `1.0`.
+    pub offsets: Vec<f64>,
+    pub kinds: Vec<ChaserKind>,
+    pub count_start: f64,
+    pub count_step: f64,
+    pub count_limit: f64,
+    pub speed_step: f64,
+    pub speed_limit: f64,
+    pub size_step: f64,
+    pub size_limit: f64,
+}
+
+impl Default for WaveSettings {
+    fn default() -> Self {
+        use settings::waves;
+        WaveSettings {
+            interval: waves::INTERVAL,
+            stagger: waves::STAGGER,
+            offsets: waves::OFFSETS.to_vec(),
+            kinds: vec![ChaserKind::Brute, ChaserKind::Hunter, ChaserKind::Drifter],
+            count_start: waves::COUNT_START,
+            count_step: waves::COUNT_STEP,
+            count_limit: waves::COUNT_LIMIT,
+            speed_step: waves::SPEED_STEP,
+            speed_limit: waves::SPEED_LIMIT,
+            size_step: waves::SIZE_STEP,
+            size_limit: waves::SIZE_LIMIT,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScoreSettings {
+    pub points_per_second: f64,
+    pub near_miss_margin: f64,
+    pub near_miss_points: f64,
+    pub streak_step: f64,
+    pub max_multiplier: f64,
+    pub streak_timeout: f64,
+}
+
+impl Default for ScoreSettings {
+    fn default() -> Self {
+        use settings::score;
+        ScoreSettings {
+            points_per_second: score::POINTS_PER_SECOND,
+            near_miss_margin: score::NEAR_MISS_MARGIN,
+            near_miss_points: score::NEAR_MISS_POINTS,
+            streak_step: score::STREAK_STEP,
+            max_multiplier: score::MAX_MULTIPLIER,
+            streak_timeout: score::STREAK_TIMEOUT,
+        }
+    }
+}
+
+impl Settings {
+    /// Where the settings file lives in the user's config directory, `None`
+    /// on platforms without one.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|directory| {
+            directory
+                .join(settings::config::DIRECTORY)
+                .join(settings::config::FILE)
+        })
+    }
+
+    /// Settings from the text of a settings file with `overrides` applied
+    /// on top, each a dotted key such as `player.speed` and a TOML value.
+    pub fn parse(text: &str, overrides: &[(String, String)]) -> Result<Self, ConfigError> {
+        let mut table: toml::Table = text.parse()?;
+        for (key, value) in overrides {
+            set(&mut table, key, value)?;
+        }
+        let settings: Settings = toml::Value::Table(table).try_into()?;
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    /// Settings from the file at `path` with `overrides` applied on top.
+    pub fn load(path: &Path, overrides: &[(String, String)]) -> Result<Self, ConfigError> {
+        let text =
+            fs::read_to_string(path).map_err(|error| ConfigError::Io(path.to_owned(), error))?;
+        Settings::parse(&text, overrides)
+    }
+
+    /// Checks every value is in range, naming the first one that isn't.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let Settings {
+            window,
+            color,
+            player,
+            chaser,
+            waves,
+            score,
+        } = self;
+        at_least_one("window.width", window.width)?;
+        at_least_one("window.height", window.height)?;
+        for (name, value) in [
+            ("color.background", color.background),
+            ("color.player", color.player),
+            ("color.chaser", color.chaser),
+            ("color.obstacle", color.obstacle),
+            ("color.text", color.text),
+            ("color.paused", color.paused),
+            ("color.game_over", color.game_over),
+        ] {
+            if !value.iter().all(|&channel| (0.0..=1.0).contains(&channel)) {
+                return Err(invalid(name, "must have every channel between 0 and 1"));
+            }
+        }
+
+        positive("player.size", player.size)?;
+        positive("player.speed", player.speed)?;
+        positive("player.acceleration", player.acceleration)?;
+        positive("player.mass", player.mass)?;
+        unit("player.restitution", player.restitution)?;
+        at_least_one("player.health", player.health)?;
+        at_least_one("player.lives", player.lives)?;
+        non_negative("player.invulnerable_time", player.invulnerable_time)?;
+        non_negative("player.respawn_time", player.respawn_time)?;
+
+        positive("chaser.size", chaser.size)?;
+        positive("chaser.speed", chaser.speed)?;
+        positive("chaser.acceleration", chaser.acceleration)?;
+        positive("chaser.mass", chaser.mass)?;
+        unit("chaser.restitution", chaser.restitution)?;
+
+        positive("waves.interval", waves.interval)?;
+        non_negative("waves.stagger", waves.stagger)?;
+        for &offset in &waves.offsets {
+            unit("waves.offsets", offset)?;
+        }
+        non_negative("waves.count_start", waves.count_start)?;
+        finite("waves.count_step", waves.count_step)?;
+        finite("waves.count_limit", waves.count_limit)?;
+        finite("waves.speed_step", waves.speed_step)?;
+        finite("waves.size_step", waves.size_step)?;
+        positive("waves.speed_limit", waves.speed_limit)?;
+        positive("waves.size_limit", waves.size_limit)?;
+
+        non_negative("score.points_per_second", score.points_per_second)?;
+        positive("score.near_miss_margin", score.near_miss_margin)?;
+        non_negative("score.near_miss_points", score.near_miss_points)?;
+        non_negative("score.streak_step", score.streak_step)?;
+        if score.max_multiplier < 1.0 || score.max_multiplier.is_nan() {
+            return Err(invalid("score.max_multiplier", "must be at least 1"));
+        }
+        non_negative("score.streak_timeout", score.streak_timeout)
+    }
+}
+
+/// Where the settings come from, as given on the command line.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Options {
+    /// Settings file asked for with `--config`.
+    pub config: Option<PathBuf>,
+    /// Values asked for with `--set key=value`, in order.
+    pub overrides: Vec<(String, String)>,
+}
+
+impl Options {
+    pub const USAGE: &'static str = "Usage: dodge [--config PATH] [--set SECTION.KEY=VALUE]...";
+
+    /// Options from the arguments after the program name.
+    pub fn parse<I>(args: I) -> Result<Self, ConfigError>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut options = Options::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| ConfigError::Usage(format!("{} needs a value", name)))
+            };
+            match arg.as_str() {
+                "--config" => options.config = Some(PathBuf::from(value("--config")?)),
+                "--set" => {
+                    let setting = value("--set")?;
+                    let Some((key, value)) = setting.split_once('=') else {
+                        return Err(ConfigError::Usage(format!(
+                            "--set takes KEY=VALUE, got {}",
+                            setting
+                        )));
+                    };
+                    options
+                        .overrides
+                        .push((key.trim().to_string(), value.trim().to_string()));
+                }
+                _ => return Err(ConfigError::Usage(format!("Unknown argument {}", arg))),
+            }
+        }
+        Ok(options)
+    }
+
+    /// The file to read settings from: the one asked for, or the default
+    /// one if there is one.
+    pub fn path(&self) -> Option<PathBuf> {
+        self.config.clone().or_else(Settings::default_path)
+    }
+
+    /// Settings from the file with the overrides on top. A file asked for
+    /// has to exist, a missing default file just means default settings.
+    pub fn settings(&self) -> Result<Settings, ConfigError> {
+        match self.path() {
+            Some(path) if self.config.is_some() || path.exists() => {
+                Settings::load(&path, &self.overrides)
+            }
+            _ => Settings::parse("", &self.overrides),
+        }
+    }
+
+    /// Watcher for the settings file, `None` on platforms without a default
+    /// one when none was asked for.
+    pub fn watcher(&self) -> Option<SettingsWatcher> {
+        self.path()
+            .map(|path| SettingsWatcher::new(path, self.overrides.clone()))
+    }
+}
+
+/// Looks out for changes to a settings file and reloads it, so values can
+/// be tuned without restarting the game.
+pub struct SettingsWatcher {
+    path: PathBuf,
+    overrides: Vec<(String, String)>,
+    /// What was in the file when it was last looked at, `None` while there
+    /// is no file. Compared as a whole, since an edit can keep both the
+    /// length and, on coarse filesystems, the modification time.
+    contents: Option<Vec<u8>>,
+    next_check_in: f64,
+}
+
+impl SettingsWatcher {
+    /// Watcher for the file at `path`, taking what is in it now as already
+    /// loaded. The overrides stay on top of every reload.
+    pub fn new(path: PathBuf, overrides: Vec<(String, String)>) -> Self {
+        SettingsWatcher {
+            contents: fs::read(&path).ok(),
+            path,
+            overrides,
+            next_check_in: settings::config::WATCH_INTERVAL,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Counts down `dt` seconds and checks the file once every
+    /// `settings::config::WATCH_INTERVAL`.
+    pub fn update(&mut self, dt: f64) -> Option<Result<Settings, ConfigError>> {
+        self.next_check_in -= dt;
+        if self.next_check_in > 0.0 {
+            return None;
+        }
+        self.next_check_in = settings::config::WATCH_INTERVAL;
+        self.check()
+    }
+
+    /// Reloads the file if it changed since the last look. A change is
+    /// reported once, so a broken file isn't complained about again until
+    /// it changes. A file that went away leaves the settings as they are.
+    pub fn check(&mut self) -> Option<Result<Settings, ConfigError>> {
+        let contents = fs::read(&self.path).ok();
+        if contents == self.contents {
+            return None;
+        }
+        self.contents = contents;
+        let contents = self.contents.as_deref()?;
+        Some(match std::str::from_utf8(contents) {
+            Ok(text) => Settings::parse(text, &self.overrides),
+            Err(error) => Err(ConfigError::Io(
+                self.path.clone(),
+                io::Error::new(io::ErrorKind::InvalidData, error),
+            )),
+        })
+    }
+}
+
+/// Sets the value at a dotted key, creating the sections on the way.
+fn set(table: &mut toml::Table, key: &str, value: &str) -> Result<(), ConfigError> {
+    let (sections, name) = match key.rsplit_once('.') {
+        Some((sections, name)) => (sections.split('.').collect(), name),
+        None => (Vec::new(), key),
+    };
+    let mut table = table;
+    for section in sections {
+        let entry = table
+            .entry(section)
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        table = entry
+            .as_table_mut()
+            .ok_or_else(|| ConfigError::Usage(format!("{} is not a section", section)))?;
+    }
+    table.insert(name.to_string(), parse_value(value));
+    Ok(())
+}
+
+/// TOML value written on the command line, taken as a string if it isn't
+/// valid TOML so names don't need quoting.
+fn parse_value(value: &str) -> toml::Value {
+    format!("value = {}", value)
+        .parse::<toml::Table>()
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(value.to_string()))
+}
+
+fn invalid(name: &str, reason: &str) -> ConfigError {
+    ConfigError::Invalid(format!("{} {}", name, reason))
+}
+
+fn positive(name: &str, value: f64) -> Result<(), ConfigError> {
+    finite(name, value)?;
+    if value > 0.0 {
+        Ok(())
+    } else {
+        Err(invalid(name, "must be greater than 0"))
+    }
+}
+
+fn non_negative(name: &str, value: f64) -> Result<(), ConfigError> {
+    finite(name, value)?;
+    if value >= 0.0 {
+        Ok(())
+    } else {
+        Err(invalid(name, "must not be negative"))
+    }
+}
+
+fn finite(name: &str, value: f64) -> Result<(), ConfigError> {
+    if value.is_finite() {
+        Ok(())
+    } else {
+        Err(invalid(name, "must be a finite number"))
+    }
+}
+
+fn unit(name: &str, value: f64) -> Result<(), ConfigError> {
+    if (0.0..=1.0).contains(&value) {
+        Ok(())
+    } else {
+        Err(invalid(name, "must be between 0 and 1"))
+    }
+}
+
+fn at_least_one(name: &str, value: u32) -> Result<(), ConfigError> {
+    if value >= 1 {
+        Ok(())
+    } else {
+        Err(invalid(name, "must be at least 1"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chaser::ChaserKind;
+    use crate::config::{ConfigError, Options, Settings, SettingsWatcher};
+    use crate::settings;
+    use std::fs;
+
+    fn args(text: &str) -> Vec<String> {
+        text.split_whitespace().map(String::from).collect()
+    }
+
+    fn set(key: &str, value: &str) -> Vec<(String, String)> {
+        vec![(key.to_string(), value.to_string())]
+    }
+
+    #[test]
+    fn parse_empty_text_returns_defaults() {
+        let result = Settings::parse("", &[]).unwrap();
+
+        assert_eq!(result, Settings::default());
+        assert_eq!(result.player.speed, settings::player::SPEED);
+        assert_eq!(result.chaser.drag(), settings::chaser::DRAG);
+    }
+
+    #[test]
+    fn parse_partial_file_keeps_other_defaults() {
+        let text = "[player]\nspeed = 300.0\n\n[waves]\nkinds = [\"hunter\"]\n";
+
+        let result = Settings::parse(text, &[]).unwrap();
+
+        assert_eq!(result.player.speed, 300.0);
+        assert_eq!(result.player.size, settings::player::SIZE);
+        assert_eq!(result.waves.kinds, vec![ChaserKind::Hunter]);
+    }
+
+    #[test]
+    fn parse_non_positive_size_returns_invalid() {
+        let result = Settings::parse("[chaser]\nsize = 0.0\n", &[]);
+
+        match result {
+            Err(ConfigError::Invalid(message)) => assert!(message.starts_with("chaser.size")),
+            other => panic!("expected invalid chaser size, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse_infinite_size_returns_invalid() {
+        let from_file = Settings::parse("[player]\nsize = inf\n", &[]);
+        let from_override = Settings::parse("", &set("player.size", "inf"));
+
+        for result in [from_file, from_override] {
+            match result {
+                Err(ConfigError::Invalid(message)) => assert!(message.starts_with("player.size")),
+                other => panic!("expected invalid player size, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn parse_non_finite_wave_step_returns_invalid() {
+        for text in [
+            "[waves]\nspeed_step = nan\n",
+            "[waves]\ncount_limit = inf\n",
+            "[waves]\nsize_step = -inf\n",
+        ] {
+            let result = Settings::parse(text, &[]);
+
+            assert!(matches!(result, Err(ConfigError::Invalid(_))), "{}", text);
+        }
+    }
+
+    #[test]
+    fn parse_broken_toml_returns_parse_error() {
+        let result = Settings::parse("[player\nspeed = ", &[]);
+
+        assert!(matches!(result, Err(ConfigError::Parse(_))));
+    }
+
+    #[test]
+    fn parse_unknown_key_returns_parse_error() {
+        let result = Settings::parse("[player]\nsped = 300.0\n", &[]);
+
+        assert!(matches!(result, Err(ConfigError::Parse(_))));
+    }
+
+    #[test]
+    fn parse_wrong_type_returns_parse_error() {
+        let result = Settings::parse("[player]\nlives = \"many\"\n", &[]);
+
+        assert!(matches!(result, Err(ConfigError::Parse(_))));
+    }
+
+    #[test]
+    fn parse_override_wins_over_file() {
+        let text = "[player]\nspeed = 300.0\n";
+
+        let result = Settings::parse(text, &set("player.speed", "400.0")).unwrap();
+
+        assert_eq!(result.player.speed, 400.0);
+    }
+
+    #[test]
+    fn parse_override_bare_word_is_string() {
+        let result = Settings::parse("", &set("waves.kinds", "[\"drifter\"]")).unwrap();
+        let invalid = Settings::parse("", &set("player.size", "big"));
+
+        assert_eq!(result.waves.kinds, vec![ChaserKind::Drifter]);
+        assert!(matches!(invalid, Err(ConfigError::Parse(_))));
+    }
+
+    #[test]
+    fn load_missing_file_returns_io_error() {
+        let path = std::env::temp_dir().join("dodge-missing-settings/settings.toml");
+
+        let result = Settings::load(&path, &[]);
+
+        assert!(matches!(result, Err(ConfigError::Io(..))));
+    }
+
+    #[test]
+    fn load_reads_file() {
+        let directory = std::env::temp_dir().join(format!("dodge-settings-{}", std::process::id()));
+        let path = directory.join("settings.toml");
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(&path, "[window]\nwidth = 640\n").unwrap();
+
+        let result = Settings::load(&path, &[]);
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(result.unwrap().window.width, 640);
+    }
+
+    #[test]
+    fn options_parse_reads_config_and_overrides() {
+        let result = Options::parse(args("--config my.toml --set player.speed=1")).unwrap();
+
+        assert_eq!(result.config.unwrap().to_str(), Some("my.toml"));
+        assert_eq!(result.overrides, set("player.speed", "1"));
+    }
+
+    #[test]
+    fn options_parse_bad_arguments_return_usage_error() {
+        for bad in ["--fast", "--config", "--set", "--set player.speed"] {
+            let result = Options::parse(args(bad));
+
+            assert!(matches!(result, Err(ConfigError::Usage(_))), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn options_settings_missing_requested_file_returns_error() {
+        let options = Options {
+            config: Some(std::env::temp_dir().join("dodge-missing-settings/settings.toml")),
+            overrides: Vec::new(),
+        };
+
+        assert!(options.settings().is_err());
+    }
+
+    fn watched(name: &str, text: &str) -> SettingsWatcher {
+        let directory = std::env::temp_dir().join(format!("dodge-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("settings.toml");
+        fs::write(&path, text).unwrap();
+        SettingsWatcher::new(path, Vec::new())
+    }
+
+    #[test]
+    fn check_unchanged_file_returns_nothing() {
+        let mut watcher = watched("unchanged", "[player]\nspeed = 300.0\n");
+
+        let result = watcher.check();
+        fs::remove_dir_all(watcher.path().parent().unwrap()).unwrap();
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn check_changed_file_returns_new_settings_once() {
+        let mut watcher = watched("changed", "[player]\nspeed = 300.0\n");
+
+        fs::write(watcher.path(), "[player]\nspeed = 350.0\nsize = 25.0\n").unwrap();
+        let result = watcher.check();
+        let again = watcher.check();
+        fs::remove_dir_all(watcher.path().parent().unwrap()).unwrap();
+
+        let result = result.unwrap().unwrap();
+        assert_eq!(result.player.speed, 350.0);
+        assert_eq!(result.player.size, 25.0);
+        assert!(again.is_none());
+    }
+
+    #[test]
+    fn check_edit_keeping_length_returns_new_settings() {
+        let mut watcher = watched("same-length", "[player]\nspeed = 300.0\n");
+
+        fs::write(watcher.path(), "[player]\nspeed = 350.0\n").unwrap();
+        let result = watcher.check();
+        fs::remove_dir_all(watcher.path().parent().unwrap()).unwrap();
+
+        assert_eq!(result.unwrap().unwrap().player.speed, 350.0);
+    }
+
+    #[test]
+    fn check_broken_file_returns_error_until_fixed() {
+        let mut watcher = watched("broken", "[player]\nspeed = 300.0\n");
+
+        fs::write(watcher.path(), "[player\nspeed = ").unwrap();
+        let broken = watcher.check();
+        let again = watcher.check();
+        fs::write(watcher.path(), "[player]\nspeed = 320.0\n").unwrap();
+        let fixed = watcher.check();
+        fs::remove_dir_all(watcher.path().parent().unwrap()).unwrap();
+
+        assert!(matches!(broken, Some(Err(ConfigError::Parse(_)))));
+        assert!(again.is_none());
+        assert_eq!(fixed.unwrap().unwrap().player.speed, 320.0);
+    }
+
+    #[test]
+    fn check_keeps_overrides_on_top() {
+        let directory =
+            std::env::temp_dir().join(format!("dodge-overridden-{}", std::process::id()));
+        let path = directory.join("settings.toml");
+        let mut watcher = SettingsWatcher::new(path.clone(), set("player.speed", "400.0"));
+
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(&path, "[player]\nspeed = 300.0\n").unwrap();
+        let result = watcher.check();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(result.unwrap().unwrap().player.speed, 400.0);
+    }
+
+    #[test]
+    fn update_checks_only_every_interval() {
+        let mut watcher = watched("interval", "[player]\nspeed = 300.0\n");
+        let interval = settings::config::WATCH_INTERVAL;
+
+        fs::write(watcher.path(), "[player]\nspeed = 350.0\n").unwrap();
+        let early = watcher.update(interval / 2.0);
+        let due = watcher.update(interval / 2.0);
+        fs::remove_dir_all(watcher.path().parent().unwrap()).unwrap();
+
+        assert!(early.is_none());
+        assert!(due.is_some());
+    }
+}
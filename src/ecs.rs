@@ -0,0 +1,345 @@
+//! Generational entity store and component storages.
+//!
+//! An `Entity` is just an index plus a generation. Destroying an entity
+//! bumps the generation of its slot, so stale handles to a reused slot are
+//! told apart from the entity living there now.
+
+/// Handle to an entity, ordered by slot index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Entity {
+    index: u32,
+    generation: u32,
+}
+
+impl Entity {
+    pub fn index(&self) -> usize {
+        self.index as usize
+    }
+}
+
+pub struct Entities {
+    generations: Vec<u32>,
+    alive: Vec<bool>,
+    free: Vec<u32>,
+}
+
+impl Default for Entities {
+    fn default() -> Self {
+        Entities::new()
+    }
+}
+
+impl Entities {
+    pub fn new() -> Self {
+        Entities {
+            generations: Vec::new(),
+            alive: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+
+    pub fn create(&mut self) -> Entity {
+        match self.free.pop() {
+            Some(index) => {
+                self.alive[index as usize] = true;
+                Entity {
+                    index,
+                    generation: self.generations[index as usize],
+                }
+            }
+            None => {
+                self.generations.push(0);
+                self.alive.push(true);
+                Entity {
+                    index: (self.generations.len() - 1) as u32,
+                    generation: 0,
+                }
+            }
+        }
+    }
+
+    /// Frees the entity's slot, returns false if it was already gone.
+    pub fn destroy(&mut self, entity: Entity) -> bool {
+        if !self.is_alive(entity) {
+            return false;
+        }
+        self.alive[entity.index()] = false;
+        self.generations[entity.index()] += 1;
+        self.free.push(entity.index);
+        true
+    }
+
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.alive.get(entity.index()).copied().unwrap_or(false)
+            && self.generations[entity.index()] == entity.generation
+    }
+
+    /// Living entities in slot order.
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.alive
+            .iter()
+            .enumerate()
+            .filter(|(_, &alive)| alive)
+            .map(move |(index, _)| Entity {
+                index: index as u32,
+                generation: self.generations[index],
+            })
+    }
+}
+
+/// One component type for any number of entities, stored by slot index.
+pub struct Components<T> {
+    slots: Vec<Option<(Entity, T)>>,
+}
+
+impl<T> Default for Components<T> {
+    fn default() -> Self {
+        Components::new()
+    }
+}
+
+impl<T> Components<T> {
+    pub fn new() -> Self {
+        Components { slots: Vec::new() }
+    }
+
+    /// Sets the entity's component, returning the one it replaces.
+    pub fn insert(&mut self, entity: Entity, component: T) -> Option<T> {
+        if self.slots.len() <= entity.index() {
+            self.slots.resize_with(entity.index() + 1, || None);
+        }
+        self.slots[entity.index()]
+            .replace((entity, component))
+            .filter(|(owner, _)| *owner == entity)
+            .map(|(_, component)| component)
+    }
+
+    pub fn remove(&mut self, entity: Entity) -> Option<T> {
+        let slot = self.slots.get_mut(entity.index())?;
+        match slot {
+            Some((owner, _)) if *owner == entity => slot.take().map(|(_, component)| component),
+            _ => None,
+        }
+    }
+
+    pub fn get(&self, entity: Entity) -> Option<&T> {
+        match self.slots.get(entity.index())? {
+            Some((owner, component)) if *owner == entity => Some(component),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
+        match self.slots.get_mut(entity.index())? {
+            Some((owner, component)) if *owner == entity => Some(component),
+            _ => None,
+        }
+    }
+
+    /// Both entities' components at once, `None` if either is missing or
+    /// they are the same entity.
+    pub fn get_pair_mut(&mut self, a: Entity, b: Entity) -> Option<(&mut T, &mut T)> {
+        if a.index() == b.index() || !self.contains(a) || !self.contains(b) {
+            return None;
+        }
+        let (low, high) = if a.index() < b.index() {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        let (head, tail) = self.slots.split_at_mut(high.index());
+        let low_component = head[low.index()].as_mut().map(|(_, component)| component)?;
+        let high_component = tail[0].as_mut().map(|(_, component)| component)?;
+        if low == a {
+            Some((low_component, high_component))
+        } else {
+            Some((high_component, low_component))
+        }
+    }
+
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.get(entity).is_some()
+    }
+
+    /// Entities with this component and the component, in slot order.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &T)> {
+        self.slots.iter().filter_map(|slot| {
+            slot.as_ref()
+                .map(|(entity, component)| (*entity, component))
+        })
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Entity, &mut T)> {
+        self.slots.iter_mut().filter_map(|slot| {
+            slot.as_mut()
+                .map(|(entity, component)| (*entity, component))
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ecs::{Components, Entities};
+
+    #[test]
+    fn create_returns_distinct_entities() {
+        let mut entities = Entities::new();
+
+        let first = entities.create();
+        let second = entities.create();
+
+        assert_ne!(first, second);
+        assert!(entities.is_alive(first));
+        assert!(entities.is_alive(second));
+    }
+
+    #[test]
+    fn destroy_living_entity_returns_true_once() {
+        let mut entities = Entities::new();
+        let entity = entities.create();
+
+        let first = entities.destroy(entity);
+        let second = entities.destroy(entity);
+
+        assert!(first);
+        assert!(!second);
+        assert!(!entities.is_alive(entity));
+    }
+
+    #[test]
+    fn create_after_destroy_reuses_slot_with_new_generation() {
+        let mut entities = Entities::new();
+        let old = entities.create();
+
+        entities.destroy(old);
+        let new = entities.create();
+
+        assert_eq!(new.index(), old.index());
+        assert_ne!(new, old);
+        assert!(!entities.is_alive(old));
+        assert!(entities.is_alive(new));
+    }
+
+    #[test]
+    fn iter_skips_destroyed_entities() {
+        let mut entities = Entities::new();
+        let first = entities.create();
+        let second = entities.create();
+        let third = entities.create();
+
+        entities.destroy(second);
+        let result: Vec<_> = entities.iter().collect();
+
+        assert_eq!(result, vec![first, third]);
+    }
+
+    #[test]
+    fn get_inserted_component_returns_it() {
+        let mut entities = Entities::new();
+        let mut components = Components::new();
+        let entity = entities.create();
+
+        components.insert(entity, 5);
+
+        assert_eq!(components.get(entity), Some(&5));
+    }
+
+    #[test]
+    fn insert_twice_returns_replaced_component() {
+        let mut entities = Entities::new();
+        let mut components = Components::new();
+        let entity = entities.create();
+
+        components.insert(entity, 5);
+        let result = components.insert(entity, 6);
+
+        assert_eq!(result, Some(5));
+        assert_eq!(components.get(entity), Some(&6));
+    }
+
+    #[test]
+    fn get_with_stale_entity_returns_none() {
+        let mut entities = Entities::new();
+        let mut components = Components::new();
+        let old = entities.create();
+        components.insert(old, 5);
+
+        entities.destroy(old);
+        let new = entities.create();
+        components.insert(new, 6);
+
+        assert_eq!(components.get(old), None);
+        assert_eq!(components.remove(old), None);
+        assert_eq!(components.get(new), Some(&6));
+    }
+
+    #[test]
+    fn remove_component_returns_it_and_leaves_none() {
+        let mut entities = Entities::new();
+        let mut components = Components::new();
+        let entity = entities.create();
+        components.insert(entity, 5);
+
+        let result = components.remove(entity);
+
+        assert_eq!(result, Some(5));
+        assert!(!components.contains(entity));
+        assert!(components.is_empty());
+    }
+
+    #[test]
+    fn get_pair_mut_returns_components_in_argument_order() {
+        let mut entities = Entities::new();
+        let mut components = Components::new();
+        let first = entities.create();
+        let second = entities.create();
+        components.insert(first, 1);
+        components.insert(second, 2);
+
+        let (a, b) = components.get_pair_mut(second, first).unwrap();
+        *a += 10;
+        *b += 20;
+
+        assert_eq!(components.get(first), Some(&21));
+        assert_eq!(components.get(second), Some(&12));
+    }
+
+    #[test]
+    fn get_pair_mut_same_entity_returns_none() {
+        let mut entities = Entities::new();
+        let mut components = Components::new();
+        let entity = entities.create();
+        components.insert(entity, 1);
+
+        let result = components.get_pair_mut(entity, entity);
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn iter_mut_visits_every_component() {
+        let mut entities = Entities::new();
+        let mut components = Components::new();
+        let first = entities.create();
+        let _without = entities.create();
+        let third = entities.create();
+        components.insert(first, 1);
+        components.insert(third, 3);
+
+        for (_, value) in components.iter_mut() {
+            *value *= 10;
+        }
+        let result: Vec<_> = components.iter().collect();
+
+        assert_eq!(result, vec![(first, &10), (third, &30)]);
+        assert_eq!(components.len(), 2);
+    }
+}
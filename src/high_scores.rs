@@ -0,0 +1,259 @@
+//! Local table of the best scores, kept in a plain text file.
+//!
+//! Every line of the file holds the points and the name, separated by a
+//! tab. Loading never fails: a missing or unreadable file is an empty
+//! table, and lines that don't parse are skipped so one bad line doesn't
+//! lose the rest. Saving writes a temporary file first and renames it over
+//! the old one, so a crash half way through leaves the previous table.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::settings;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct HighScore {
+    pub name: String,
+    pub points: u64,
+}
+
+/// Best scores first, at most `settings::high_scores::ENTRIES` of them.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HighScores {
+    entries: Vec<HighScore>,
+}
+
+impl HighScores {
+    pub fn new() -> Self {
+        HighScores {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Where the table lives in the user's data directory, `None` on
+    /// platforms without one.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|directory| {
+            directory
+                .join(settings::high_scores::DIRECTORY)
+                .join(settings::high_scores::FILE)
+        })
+    }
+
+    /// Table read from `path`, keeping whatever can be made sense of.
+    pub fn load(path: &Path) -> Self {
+        match fs::read(path) {
+            Ok(bytes) => HighScores::parse(&String::from_utf8_lossy(&bytes)),
+            Err(_) => HighScores::new(),
+        }
+    }
+
+    /// Writes the table to `path`, creating its directory if needed.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        let temporary = path.with_extension("tmp");
+        fs::write(&temporary, self.to_text())?;
+        fs::rename(&temporary, path)
+    }
+
+    /// Table from the text of a high score file, skipping bad lines.
+    pub fn parse(text: &str) -> Self {
+        let mut table = HighScores::new();
+        for line in text.lines() {
+            let Some((points, name)) = line.split_once('\t') else {
+                continue;
+            };
+            if let Ok(points) = points.trim().parse() {
+                table.insert(name, points);
+            }
+        }
+        table
+    }
+
+    pub fn to_text(&self) -> String {
+        self.entries
+            .iter()
+            .map(|entry| format!("{}\t{}\n", entry.points, entry.name))
+            .collect()
+    }
+
+    pub fn entries(&self) -> &[HighScore] {
+        &self.entries
+    }
+
+    /// Whether `points` would make it onto the table.
+    pub fn qualifies(&self, points: u64) -> bool {
+        points > 0
+            && (self.entries.len() < settings::high_scores::ENTRIES
+                || self.entries.iter().any(|entry| points > entry.points))
+    }
+
+    /// Adds the score below any equal ones and returns its zero-based rank,
+    /// `None` if it didn't make the table. The name is cleaned up with
+    /// `clean_name` first.
+    pub fn insert(&mut self, name: &str, points: u64) -> Option<usize> {
+        if !self.qualifies(points) {
+            return None;
+        }
+        let rank = self
+            .entries
+            .iter()
+            .position(|entry| points > entry.points)
+            .unwrap_or(self.entries.len());
+        let name = clean_name(name);
+        self.entries.insert(rank, HighScore { name, points });
+        self.entries.truncate(settings::high_scores::ENTRIES);
+        Some(rank)
+    }
+}
+
+/// Name with only letters, digits, `-` and `_` left, cut to the maximum
+/// length, or the default name if nothing is left.
+pub fn clean_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .filter(|&character| is_name_character(character))
+        .take(settings::high_scores::NAME_LENGTH)
+        .collect();
+    if name.is_empty() {
+        settings::high_scores::DEFAULT_NAME.to_string()
+    } else {
+        name
+    }
+}
+
+/// Whether the character can be part of a name.
+pub fn is_name_character(character: char) -> bool {
+    character.is_alphanumeric() || character == '-' || character == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::high_scores::{clean_name, HighScores};
+    use crate::settings;
+    use std::fs;
+
+    fn full_table() -> HighScores {
+        let mut table = HighScores::new();
+        for points in 1..=settings::high_scores::ENTRIES as u64 {
+            table.insert("AAA", points * 100);
+        }
+        table
+    }
+
+    #[test]
+    fn insert_keeps_best_first() {
+        let mut table = HighScores::new();
+
+        table.insert("low", 10);
+        let rank = table.insert("high", 30);
+        table.insert("middle", 20);
+
+        assert_eq!(rank, Some(0));
+        let names: Vec<_> = table.entries().iter().map(|entry| &entry.name).collect();
+        assert_eq!(names, ["high", "middle", "low"]);
+    }
+
+    #[test]
+    fn insert_full_table_drops_lowest() {
+        let mut table = full_table();
+
+        let rank = table.insert("new", 150);
+
+        assert_eq!(rank, Some(settings::high_scores::ENTRIES - 1));
+        assert_eq!(table.entries().len(), settings::high_scores::ENTRIES);
+        assert_eq!(table.entries().last().unwrap().points, 150);
+    }
+
+    #[test]
+    fn qualifies_score_too_low_for_full_table_returns_false() {
+        let table = full_table();
+
+        assert!(!table.qualifies(100));
+        assert!(!HighScores::new().qualifies(0));
+        assert!(table.qualifies(101));
+    }
+
+    #[test]
+    fn clean_name_strips_separators_and_caps_length() {
+        assert_eq!(clean_name("a\tb\nc d"), "abcd");
+        assert_eq!(clean_name(""), settings::high_scores::DEFAULT_NAME);
+        assert_eq!(
+            clean_name(&"x".repeat(100)).len(),
+            settings::high_scores::NAME_LENGTH
+        );
+    }
+
+    #[test]
+    fn parse_round_trips_to_text() {
+        let mut table = HighScores::new();
+        table.insert("first", 200);
+        table.insert("second", 100);
+
+        let result = HighScores::parse(&table.to_text());
+
+        assert_eq!(result, table);
+    }
+
+    #[test]
+    fn parse_skips_corrupt_lines() {
+        let text = "100\tgood\nnot a score\n-5\tnegative\n\t\n300\tbetter\n12x\tbad\n";
+
+        let result = HighScores::parse(text);
+
+        assert_eq!(result.entries().len(), 2);
+        assert_eq!(result.entries()[0].name, "better");
+        assert_eq!(result.entries()[1].name, "good");
+    }
+
+    #[test]
+    fn parse_unsorted_oversized_file_keeps_best() {
+        let text: String = (1..=20)
+            .map(|points| format!("{}\tname\n", points))
+            .collect();
+
+        let result = HighScores::parse(&text);
+
+        assert_eq!(result.entries().len(), settings::high_scores::ENTRIES);
+        assert_eq!(result.entries()[0].points, 20);
+    }
+
+    #[test]
+    fn load_missing_file_returns_empty_table() {
+        let path = std::env::temp_dir().join("dodge-test-missing/high_scores.txt");
+
+        let result = HighScores::load(&path);
+
+        assert!(result.entries().is_empty());
+    }
+
+    #[test]
+    fn save_then_load_returns_same_table() {
+        let directory = std::env::temp_dir().join(format!("dodge-test-{}", std::process::id()));
+        let path = directory.join("nested").join("high_scores.txt");
+        let mut table = HighScores::new();
+        table.insert("saved", 42);
+
+        table.save(&path).unwrap();
+        let result = HighScores::load(&path);
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(result, table);
+    }
+
+    #[test]
+    fn load_binary_garbage_returns_empty_table() {
+        let directory = std::env::temp_dir().join(format!("dodge-garbage-{}", std::process::id()));
+        let path = directory.join("high_scores.txt");
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(&path, [0xff, 0xfe, 0x00, 0x09, 0x80]).unwrap();
+
+        let result = HighScores::load(&path);
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert!(result.entries().is_empty());
+    }
+}
@@ -0,0 +1,362 @@
+//! What the heads-up display says and where.
+//!
+//! The layout is worked out here against the window size in the game's
+//! settings, as a list of labels the front-end draws with whatever font it
+//! has. Status lines sit in the top corners during a game, the frame rate
+//! in the bottom right corner, and scene messages in the middle of the
+//! window.
+
+use crate::scene::{Game, Scene};
+use crate::settings::{high_scores, hud};
+use crate::vector2::Vector2;
+
+/// Which point of the text its position is.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Align {
+    /// Position is where the text starts.
+    Left,
+    /// Position is the middle of the text.
+    Center,
+    /// Position is where the text ends.
+    Right,
+}
+
+/// Line of text, positioned by its baseline.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Label {
+    pub text: String,
+    pub position: Vector2,
+    pub align: Align,
+    pub size: u32,
+}
+
+impl Label {
+    pub fn new(text: String, position: Vector2, align: Align, size: u32) -> Self {
+        Label {
+            text,
+            position,
+            align,
+            size,
+        }
+    }
+}
+
+/// Frames per second, averaged over `settings::hud::FPS_INTERVAL`.
+pub struct FpsCounter {
+    frames: u32,
+    elapsed: f64,
+    fps: f64,
+}
+
+impl FpsCounter {
+    pub fn new() -> Self {
+        FpsCounter {
+            frames: 0,
+            elapsed: 0.0,
+            fps: 0.0,
+        }
+    }
+
+    /// Counts a frame that took `dt` seconds.
+    pub fn frame(&mut self, dt: f64) {
+        self.frames += 1;
+        self.elapsed += dt;
+        if self.elapsed >= hud::FPS_INTERVAL {
+            self.fps = self.frames as f64 / self.elapsed;
+            self.frames = 0;
+            self.elapsed = 0.0;
+        }
+    }
+
+    /// Average over the last full interval, `0.0` before the first one.
+    pub fn get_fps(&self) -> f64 {
+        self.fps
+    }
+}
+
+impl Default for FpsCounter {
+    fn default() -> Self {
+        FpsCounter::new()
+    }
+}
+
+/// Labels to draw over the game in its current scene.
+pub fn layout(game: &Game, fps: f64) -> Vec<Label> {
+    let mut labels = Vec::new();
+    match game.scene() {
+        Scene::Title => {
+            let mut lines = vec![String::from("Enter to start, Esc to quit")];
+            if let Some(best) = game.high_scores().entries().first() {
+                lines.push(format!("Best: {} {}", best.name, best.points));
+            }
+            message(&mut labels, game, "DODGE", &lines);
+        }
+        Scene::Playing => status(&mut labels, game),
+        Scene::Paused => {
+            status(&mut labels, game);
+            message(
+                &mut labels,
+                game,
+                "PAUSED",
+                &[String::from("P to resume, Esc for title")],
+            );
+        }
+        Scene::GameOver => {
+            status(&mut labels, game);
+            let mut lines = vec![
+                format!("Score {}", points(game)),
+                String::from("Enter to play again, Esc for title"),
+                String::new(),
+            ];
+            lines.extend(
+                game.high_scores()
+                    .entries()
+                    .iter()
+                    .enumerate()
+                    .map(|(rank, entry)| {
+                        format!(
+                            "{:>2}. {:<width$} {:>8}",
+                            rank + 1,
+                            entry.name,
+                            entry.points,
+                            width = high_scores::NAME_LENGTH
+                        )
+                    }),
+            );
+            message(&mut labels, game, "GAME OVER", &lines);
+        }
+        Scene::EnterName => {
+            status(&mut labels, game);
+            message(
+                &mut labels,
+                game,
+                "NEW HIGH SCORE",
+                &[
+                    format!("Score {}", points(game)),
+                    format!("Name: {}_", game.name()),
+                    String::from("Enter to save, Esc to skip"),
+                ],
+            );
+        }
+    }
+    let (width, height) = window_size(game);
+    labels.push(Label::new(
+        format!("{:.0} FPS", fps),
+        Vector2::new(width - hud::MARGIN, height - hud::MARGIN),
+        Align::Right,
+        hud::FONT_SIZE,
+    ));
+    labels
+}
+
+/// Score, health, lives and wave in the top corners.
+fn status(labels: &mut Vec<Label>, game: &Game) {
+    let world = game.world();
+    let (width, _) = window_size(game);
+    let top = hud::MARGIN + hud::FONT_SIZE as f64;
+    let line = line_height(hud::FONT_SIZE);
+
+    let mut left = Vec::new();
+    if let Some(score) = world.score() {
+        left.push(format!(
+            "Score {}  x{:.1}",
+            score.get_points(),
+            score.multiplier()
+        ));
+    }
+    if let Some(player) = world.player() {
+        if let Some(health) = world.healths.get(player) {
+            left.push(format!(
+                "Health {}/{}",
+                health.get_current(),
+                health.get_max()
+            ));
+        }
+        if let Some(player) = world.players.get(player) {
+            left.push(format!("Lives {}", player.get_lives()));
+        }
+    }
+    for (i, text) in left.into_iter().enumerate() {
+        labels.push(Label::new(
+            text,
+            Vector2::new(hud::MARGIN, top + line * i as f64),
+            Align::Left,
+            hud::FONT_SIZE,
+        ));
+    }
+    labels.push(Label::new(
+        format!("Wave {}", world.wave()),
+        Vector2::new(width - hud::MARGIN, top),
+        Align::Right,
+        hud::FONT_SIZE,
+    ));
+}
+
+/// Heading with lines under it, centred in the window as a block.
+fn message(labels: &mut Vec<Label>, game: &Game, heading: &str, lines: &[String]) {
+    let (width, height) = window_size(game);
+    let heading_height = line_height(hud::TITLE_SIZE);
+    let line = line_height(hud::FONT_SIZE);
+    let block = heading_height + line * lines.len() as f64;
+    let top = (height - block) / 2.0;
+
+    labels.push(Label::new(
+        heading.to_string(),
+        Vector2::new(width / 2.0, top + hud::TITLE_SIZE as f64),
+        Align::Center,
+        hud::TITLE_SIZE,
+    ));
+    for (i, text) in lines.iter().enumerate() {
+        labels.push(Label::new(
+            text.clone(),
+            Vector2::new(width / 2.0, top + heading_height + line * (i + 1) as f64),
+            Align::Center,
+            hud::FONT_SIZE,
+        ));
+    }
+}
+
+fn points(game: &Game) -> u64 {
+    game.world().score().map_or(0, |score| score.get_points())
+}
+
+fn line_height(size: u32) -> f64 {
+    size as f64 * hud::LINE_SPACING
+}
+
+fn window_size(game: &Game) -> (f64, f64) {
+    let window = game.settings().window;
+    (window.width as f64, window.height as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::Settings;
+    use crate::high_scores::HighScores;
+    use crate::hud::{layout, Align, FpsCounter, Label};
+    use crate::scene::{Command, Game, Scene};
+    use crate::settings;
+
+    fn game() -> Game {
+        let (width, height) = settings::window::SIZE;
+        Game::with_high_scores(width as f64, height as f64, HighScores::new(), None)
+    }
+
+    /// Game played until its only life is lost, against `high_scores`.
+    fn game_over(high_scores: HighScores) -> Game {
+        let mut settings = Settings::default();
+        settings.player.health = 1;
+        settings.player.lives = 1;
+        let mut game = Game::with_settings(settings, high_scores, None);
+        game.command(Command::Confirm);
+        for _ in 0..(60.0 / settings::simulation::STEP) as u32 {
+            if game.scene() != Scene::Playing {
+                break;
+            }
+            game.update(settings::simulation::STEP);
+        }
+        game
+    }
+
+    fn full_table(points: u64) -> HighScores {
+        let mut high_scores = HighScores::new();
+        for _ in 0..settings::high_scores::ENTRIES {
+            high_scores.insert("ABCDEFGHIJKL", points);
+        }
+        high_scores
+    }
+
+    fn find<'a>(labels: &'a [Label], prefix: &str) -> &'a Label {
+        labels
+            .iter()
+            .find(|label| label.text.starts_with(prefix))
+            .unwrap()
+    }
+
+    #[test]
+    fn frame_averages_over_interval() {
+        let mut counter = FpsCounter::new();
+        let dt = settings::hud::FPS_INTERVAL / 30.0;
+
+        for _ in 0..29 {
+            counter.frame(dt);
+        }
+        let before = counter.get_fps();
+        counter.frame(dt * 1.01);
+
+        assert_eq!(before, 0.0);
+        assert!((counter.get_fps() - 60.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn layout_playing_shows_status_in_top_corners() {
+        let mut game = game();
+        game.command(Command::Confirm);
+
+        let result = layout(&game, 60.0);
+
+        let score = find(&result, "Score");
+        let wave = find(&result, "Wave");
+        assert_eq!(score.align, Align::Left);
+        assert_eq!(wave.align, Align::Right);
+        assert_eq!(score.position.y, wave.position.y);
+        assert!(score.position.y < settings::window::SIZE.1 as f64 / 4.0);
+        assert_eq!(
+            find(&result, "Health").text,
+            format!(
+                "Health {}/{}",
+                settings::player::HEALTH,
+                settings::player::HEALTH
+            )
+        );
+        assert_eq!(find(&result, "60 FPS").align, Align::Right);
+    }
+
+    #[test]
+    fn layout_paused_centres_message() {
+        let mut game = game();
+        game.command(Command::Confirm);
+        game.command(Command::Pause);
+
+        let result = layout(&game, 60.0);
+
+        let paused = find(&result, "PAUSED");
+        assert_eq!(paused.align, Align::Center);
+        assert_eq!(paused.position.x, settings::window::SIZE.0 as f64 / 2.0);
+        assert_eq!(paused.size, settings::hud::TITLE_SIZE);
+    }
+
+    #[test]
+    fn layout_title_has_no_status() {
+        let result = layout(&game(), 60.0);
+
+        assert!(result.iter().any(|label| label.text == "DODGE"));
+        assert!(!result.iter().any(|label| label.text.starts_with("Score")));
+    }
+
+    #[test]
+    fn layout_every_scene_stays_inside_window() {
+        let (width, height) = settings::window::SIZE;
+        let mut game = game();
+        let mut scenes = vec![layout(&game, 60.0)];
+        game.command(Command::Confirm);
+        scenes.push(layout(&game, 60.0));
+        game.command(Command::Pause);
+        scenes.push(layout(&game, 60.0));
+        let over = game_over(full_table(u64::MAX));
+        scenes.push(layout(&over, 60.0));
+        let enter_name = game_over(HighScores::new());
+        scenes.push(layout(&enter_name, 60.0));
+
+        assert_eq!(over.scene(), Scene::GameOver);
+        assert_eq!(enter_name.scene(), Scene::EnterName);
+        assert!(scenes[3]
+            .iter()
+            .any(|label| label.text.starts_with("10. ABCDEFGHIJKL ")));
+
+        for label in scenes.iter().flatten() {
+            assert!(label.position.x >= 0.0 && label.position.x <= width as f64);
+            assert!(label.position.y >= 0.0 && label.position.y <= height as f64);
+        }
+    }
+}
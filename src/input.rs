@@ -0,0 +1,31 @@
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KeyState {
+    Pressed,
+    NotPressed,
+}
+
+/// Movement keys held down, independent of the window backend.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Input {
+    pub up: KeyState,
+    pub down: KeyState,
+    pub left: KeyState,
+    pub right: KeyState,
+}
+
+impl Input {
+    pub fn new() -> Self {
+        Input {
+            up: KeyState::NotPressed,
+            down: KeyState::NotPressed,
+            left: KeyState::NotPressed,
+            right: KeyState::NotPressed,
+        }
+    }
+}
+
+impl Default for Input {
+    fn default() -> Self {
+        Input::new()
+    }
+}
@@ -0,0 +1,17 @@
+//! Collision layer bits.
+//!
+//! Every collider sits on one or more layers and has a mask of the layers it
+//! reacts to. Two colliders only interact when each one's layer is in the
+//! other's mask.
+
+pub const PLAYER: u32 = 1 << 0;
+pub const ENEMY: u32 = 1 << 1;
+pub const PROJECTILE: u32 = 1 << 2;
+pub const PICKUP: u32 = 1 << 3;
+pub const WALL: u32 = 1 << 4;
+
+pub const ALL: u32 = u32::MAX;
+
+pub fn interacts(layer: u32, mask: u32, other_layer: u32, other_mask: u32) -> bool {
+    layer & other_mask != 0 && other_layer & mask != 0
+}
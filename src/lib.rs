@@ -0,0 +1,46 @@
+//! Dodge game simulation: geometry, collision detection, physics and the
+//! world stepping API, independent of any window or renderer.
+
+pub mod aabb;
+pub mod ai;
+pub mod arena;
+pub mod broad_phase;
+pub mod chaser;
+pub mod collider;
+pub mod collides;
+pub mod collision_events;
+pub mod components;
+pub mod config;
+pub mod ecs;
+pub mod high_scores;
+pub mod hud;
+pub mod input;
+pub mod layer;
+pub mod manifold;
+pub mod navigation;
+pub mod physics;
+pub mod player;
+pub mod position;
+pub mod proximity;
+pub mod ray;
+mod sat;
+pub mod scene;
+pub mod score;
+pub mod settings;
+pub mod spawner;
+pub mod steering;
+mod sweep;
+pub mod systems;
+pub mod timestep;
+pub mod vector2;
+pub mod world;
+
+pub use crate::chaser::Chaser;
+pub use crate::collider::Collider;
+pub use crate::collides::Collides;
+pub use crate::ecs::Entity;
+pub use crate::input::{Input, KeyState};
+pub use crate::player::Player;
+pub use crate::position::Position;
+pub use crate::vector2::Vector2;
+pub use crate::world::World;
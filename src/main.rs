@@ -3,95 +3,128 @@ extern crate graphics;
 extern crate opengl_graphics;
 extern crate piston;
 
-mod chaser;
-mod collider;
-mod collides;
-mod player;
-mod position;
-mod settings;
-mod vector2;
+mod render;
 
-use crate::chaser::Chaser;
-use crate::collides::Collides;
-use crate::position::Position;
-use crate::settings::color;
+use dodge::config::{ConfigError, Options};
+use dodge::hud::{self, FpsCounter};
+use dodge::scene::{Command, Game};
+use dodge::{Input, KeyState};
 use glutin_window::GlutinWindow as Window;
-use graphics::clear;
-use opengl_graphics::{GlGraphics, OpenGL};
+use opengl_graphics::{GlGraphics, GlyphCache, OpenGL, TextureSettings};
 use piston::event_loop::{EventSettings, Events};
-use piston::input::{Key, ReleaseEvent, RenderEvent, UpdateEvent};
-use piston::window::WindowSettings;
+use piston::input::{Key, ReleaseEvent, RenderEvent, TextEvent, UpdateEvent};
+use piston::window::{Window as _, WindowSettings};
 use piston::Button::Keyboard;
 use piston_window::PressEvent;
-use player::{KeyState, Player};
-use vector2::Vector2;
+use std::env;
+use std::process;
+use std::time::Instant;
+
+const FONT: &[u8] = include_bytes!("../assets/fonts/DejaVuSansMono.ttf");
 
 fn main() {
-    let (width, height) = settings::window::SIZE;
+    let loaded = Options::parse(env::args().skip(1))
+        .and_then(|options| Ok((options.settings()?, options.watcher())));
+    let (settings, mut watcher) = match loaded {
+        Ok(loaded) => loaded,
+        Err(error @ ConfigError::Usage(_)) => {
+            eprintln!("{}\n{}", error, Options::USAGE);
+            process::exit(2);
+        }
+        Err(error) => {
+            eprintln!("Invalid settings: {}", error);
+            process::exit(1);
+        }
+    };
+    let (width, height) = (settings.window.width, settings.window.height);
     // Change this to OpenGL::V2_1 if not working.
     let opengl = OpenGL::V3_2;
 
     // Create an Glutin window.
     let mut window: Window = WindowSettings::new("square", [width, height])
         .graphics_api(opengl)
-        .exit_on_esc(true)
+        .exit_on_esc(false)
         .build()
         .unwrap();
 
     // Create a new game and run it.
     let mut gl = GlGraphics::new(opengl);
-    let mut player = Player::new(Vector2::new(width as f64 / 2.0, height as f64 / 2.0));
-    let mut chaser = Chaser::new(Vector2::new(0.0, 0.0));
+    let mut game = Game::new(settings);
+    let mut glyphs = GlyphCache::from_bytes(FONT, (), TextureSettings::new()).unwrap();
+    let mut fps = FpsCounter::new();
+    let mut last_frame = Instant::now();
 
     // Key state
-    let mut up_key = KeyState::NotPressed;
-    let mut down_key = KeyState::NotPressed;
-    let mut left_key = KeyState::NotPressed;
-    let mut right_key = KeyState::NotPressed;
+    let mut input = Input::new();
+    // Collider outlines, toggled with F1.
+    let mut debug = false;
 
     let mut events = Events::new(EventSettings::new());
     while let Some(e) = events.next(&mut window) {
         if let Some(args) = e.render_args() {
+            let now = Instant::now();
+            fps.frame((now - last_frame).as_secs_f64());
+            last_frame = now;
+            let labels = hud::layout(&game, fps.get_fps());
             gl.draw(args.viewport(), |c, gl| {
-                // Clear the screen.
-                clear(color::GREY, gl);
-
-                chaser.draw(c, gl);
-
-                player.draw(c, gl);
+                render::draw_game(&game, c, gl);
+                if debug {
+                    render::draw_debug(game.world(), c, gl);
+                }
+                render::draw_hud(&labels, game.settings().color.text, &mut glyphs, c, gl);
             });
         }
 
         if let Some(press_args) = e.press_args() {
             match press_args {
-                Keyboard(Key::W) => up_key = KeyState::Pressed,
-                Keyboard(Key::S) => down_key = KeyState::Pressed,
-                Keyboard(Key::A) => left_key = KeyState::Pressed,
-                Keyboard(Key::D) => right_key = KeyState::Pressed,
+                Keyboard(Key::W) => input.up = KeyState::Pressed,
+                Keyboard(Key::S) => input.down = KeyState::Pressed,
+                Keyboard(Key::A) => input.left = KeyState::Pressed,
+                Keyboard(Key::D) => input.right = KeyState::Pressed,
+                Keyboard(Key::Return) | Keyboard(Key::Space) => game.command(Command::Confirm),
+                Keyboard(Key::Backspace) => game.command(Command::Erase),
+                Keyboard(Key::P) => game.command(Command::Pause),
+                Keyboard(Key::Escape) => game.command(Command::Back),
+                Keyboard(Key::F1) => debug = !debug,
                 _ => (),
             }
-            player.input(left_key, right_key, up_key, down_key);
+            game.set_input(input);
+            if game.should_quit() {
+                window.set_should_close(true);
+            }
+        }
+
+        if let Some(text) = e.text_args() {
+            for character in text.chars() {
+                game.type_character(character);
+            }
         }
 
         if let Some(release_args) = e.release_args() {
             match release_args {
-                Keyboard(Key::W) => up_key = KeyState::NotPressed,
-                Keyboard(Key::S) => down_key = KeyState::NotPressed,
-                Keyboard(Key::A) => left_key = KeyState::NotPressed,
-                Keyboard(Key::D) => right_key = KeyState::NotPressed,
+                Keyboard(Key::W) => input.up = KeyState::NotPressed,
+                Keyboard(Key::S) => input.down = KeyState::NotPressed,
+                Keyboard(Key::A) => input.left = KeyState::NotPressed,
+                Keyboard(Key::D) => input.right = KeyState::NotPressed,
                 _ => (),
             }
-            player.input(left_key, right_key, up_key, down_key);
+            game.set_input(input);
         }
 
         if let Some(args) = e.update_args() {
-            player.update(args.dt);
-            chaser.update(args.dt, player.get_position());
-            if player.collides_with(&mut chaser) {
-                player.damage();
-            } else {
-                player.normal();
+            if let Some(watcher) = watcher.as_mut() {
+                match watcher.update(args.dt) {
+                    Some(Ok(settings)) => {
+                        eprintln!("Reloaded settings from {}", watcher.path().display());
+                        game.apply_settings(settings);
+                    }
+                    Some(Err(error)) => {
+                        eprintln!("Invalid settings, keeping the last good ones: {}", error)
+                    }
+                    None => (),
+                }
             }
+            game.update(args.dt);
         }
     }
 }
@@ -0,0 +1,61 @@
+use crate::vector2::Vector2;
+
+/// Contact between two overlapping colliders `a` and `b`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Manifold {
+    /// Unit vector pointing from `a` towards `b`.
+    pub normal: Vector2,
+    /// How far the shapes overlap along `normal`.
+    pub depth: f64,
+    /// World-space point in the middle of the overlap.
+    pub point: Vector2,
+}
+
+impl Manifold {
+    /// Smallest translation that moves `a` out of `b`.
+    pub fn minimum_translation(&self) -> Vector2 {
+        -self.normal * self.depth
+    }
+
+    /// The same contact seen from `b`.
+    pub fn flipped(self) -> Self {
+        Manifold {
+            normal: -self.normal,
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::manifold::Manifold;
+    use crate::vector2::Vector2;
+
+    #[test]
+    fn minimum_translation_points_against_normal() {
+        let manifold = Manifold {
+            normal: Vector2::new(1.0, 0.0),
+            depth: 2.0,
+            point: Vector2::new(0.0, 0.0),
+        };
+
+        let result = manifold.minimum_translation();
+
+        assert_eq!(result, Vector2::new(-2.0, 0.0));
+    }
+
+    #[test]
+    fn flipped_reverses_normal_and_keeps_depth() {
+        let manifold = Manifold {
+            normal: Vector2::new(0.0, 1.0),
+            depth: 2.0,
+            point: Vector2::new(1.0, 1.0),
+        };
+
+        let result = manifold.flipped();
+
+        assert_eq!(result.normal, Vector2::new(0.0, -1.0));
+        assert_eq!(result.depth, 2.0);
+        assert_eq!(result.point, Vector2::new(1.0, 1.0));
+    }
+}
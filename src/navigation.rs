@@ -0,0 +1,455 @@
+//! Grid based pathfinding around static obstacles.
+//!
+//! The arena is cut into square cells, and a cell is blocked when a circle
+//! of the grid's clearance at its center would touch an obstacle. Paths are
+//! found with A* over the open cells, moving diagonally only where both
+//! neighbouring cells are open, and then smoothed by skipping every
+//! waypoint that can be seen past. A `Route` keeps a path between calls so
+//! it only has to be found again when the target moves to another cell or
+//! the path gets old.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
+
+use crate::collider::Collider;
+use crate::settings;
+use crate::vector2::Vector2;
+
+type Cell = (usize, usize);
+
+pub struct NavGrid {
+    cell_size: f64,
+    columns: usize,
+    rows: usize,
+    blocked: Vec<bool>,
+}
+
+impl NavGrid {
+    /// Grid over a `width` by `height` area with its corner at the origin.
+    pub fn new(
+        width: f64,
+        height: f64,
+        cell_size: f64,
+        clearance: f64,
+        obstacles: &[Collider],
+    ) -> Self {
+        if cell_size <= 0.0 {
+            panic!("Cell size of navigation grid must be greater than 0");
+        }
+        let columns = ((width / cell_size).ceil() as usize).max(1);
+        let rows = ((height / cell_size).ceil() as usize).max(1);
+        let mut grid = NavGrid {
+            cell_size,
+            columns,
+            rows,
+            blocked: vec![false; columns * rows],
+        };
+        for row in 0..rows {
+            for column in 0..columns {
+                let probe = Collider::new(grid.center((column, row)), clearance);
+                let index = grid.index((column, row));
+                grid.blocked[index] = obstacles
+                    .iter()
+                    .any(|obstacle| obstacle.is_enabled() && probe.collides_with(obstacle));
+            }
+        }
+        grid
+    }
+
+    pub fn is_blocked(&self, point: Vector2) -> bool {
+        self.blocked_cell(self.cell_of(point))
+    }
+
+    /// Waypoints from `from` to `to` ending exactly at `to`, `from` itself
+    /// left out. `None` if no open cells connect the two.
+    pub fn find_path(&self, from: Vector2, to: Vector2) -> Option<Vec<Vector2>> {
+        let start = self.nearest_open(self.cell_of(from))?;
+        let goal = self.nearest_open(self.cell_of(to))?;
+        let cells = self.search(start, goal)?;
+
+        let mut points = vec![from];
+        points.extend(cells.into_iter().map(|cell| self.center(cell)));
+        points.push(to);
+        Some(self.smooth(&points))
+    }
+
+    /// Whether a straight line between the points stays on open cells. The
+    /// cells the points are in don't count, so a line from or to a point
+    /// hugging an obstacle can still be walkable.
+    pub fn is_walkable(&self, from: Vector2, to: Vector2) -> bool {
+        let ends = (self.cell_of(from), self.cell_of(to));
+        let samples = ((to - from).magnitude() / (self.cell_size / 4.0)).ceil() as usize;
+        (0..=samples).all(|i| {
+            let t = if samples == 0 {
+                0.0
+            } else {
+                i as f64 / samples as f64
+            };
+            let cell = self.cell_of(Vector2::lerp(from, to, t));
+            cell == ends.0 || cell == ends.1 || !self.blocked_cell(cell)
+        })
+    }
+
+    /// Next waypoint on the way from `from` to `to`, following the path in
+    /// `route` and finding a new one when `to` has moved to another cell or
+    /// the path is older than `settings::navigation::REPLAN_INTERVAL`.
+    /// `None` if no open cells connect the two.
+    pub fn follow(
+        &self,
+        route: &mut Route,
+        from: Vector2,
+        to: Vector2,
+        dt: f64,
+    ) -> Option<Vector2> {
+        let goal = self.cell_of(to);
+        route.age += dt;
+        if route.goal != Some(goal) || route.age >= settings::navigation::REPLAN_INTERVAL {
+            route.waypoints = self.find_path(from, to).unwrap_or_default();
+            route.goal = Some(goal);
+            route.age = 0.0;
+        }
+        // The target moves about within its cell without a new path.
+        if let Some(last) = route.waypoints.last_mut() {
+            *last = to;
+        }
+        while route.waypoints.len() > 1
+            && (route.waypoints[0] - from).magnitude() < self.cell_size / 2.0
+        {
+            route.waypoints.remove(0);
+        }
+        route.waypoints.first().copied()
+    }
+
+    fn cell_of(&self, point: Vector2) -> Cell {
+        let clamp = |value: f64, count: usize| {
+            ((value / self.cell_size).floor().max(0.0) as usize).min(count - 1)
+        };
+        (clamp(point.x, self.columns), clamp(point.y, self.rows))
+    }
+
+    fn center(&self, (column, row): Cell) -> Vector2 {
+        Vector2::new(
+            (column as f64 + 0.5) * self.cell_size,
+            (row as f64 + 0.5) * self.cell_size,
+        )
+    }
+
+    fn blocked_cell(&self, cell: Cell) -> bool {
+        self.blocked[self.index(cell)]
+    }
+
+    /// Closest open cell by breadth first search, the cell itself if open.
+    fn nearest_open(&self, cell: Cell) -> Option<Cell> {
+        let mut visited = vec![false; self.blocked.len()];
+        let mut queue = VecDeque::from([cell]);
+        visited[self.index(cell)] = true;
+        while let Some(current) = queue.pop_front() {
+            if !self.blocked_cell(current) {
+                return Some(current);
+            }
+            for (neighbour, _) in self.neighbours(current, false) {
+                if !visited[self.index(neighbour)] {
+                    visited[self.index(neighbour)] = true;
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+        None
+    }
+
+    /// A* from `start` to `goal`, both open, returning the cells in order.
+    fn search(&self, start: Cell, goal: Cell) -> Option<Vec<Cell>> {
+        let mut cost = vec![f64::INFINITY; self.blocked.len()];
+        let mut came_from: Vec<Option<Cell>> = vec![None; self.blocked.len()];
+        let mut open = BinaryHeap::new();
+        cost[self.index(start)] = 0.0;
+        open.push(Node {
+            estimate: self.heuristic(start, goal),
+            cell: start,
+        });
+
+        while let Some(Node { estimate, cell }) = open.pop() {
+            if cell == goal {
+                let mut path = vec![goal];
+                while let Some(previous) = came_from[self.index(*path.last().unwrap())] {
+                    path.push(previous);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            let cell_cost = cost[self.index(cell)];
+            // Stale entry for a cell that was reached more cheaply since.
+            if estimate > cell_cost + self.heuristic(cell, goal) {
+                continue;
+            }
+            for (neighbour, step) in self.neighbours(cell, true) {
+                if self.blocked_cell(neighbour) {
+                    continue;
+                }
+                let neighbour_cost = cell_cost + step;
+                if neighbour_cost < cost[self.index(neighbour)] {
+                    cost[self.index(neighbour)] = neighbour_cost;
+                    came_from[self.index(neighbour)] = Some(cell);
+                    open.push(Node {
+                        estimate: neighbour_cost + self.heuristic(neighbour, goal),
+                        cell: neighbour,
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    /// Octile distance, exact on an empty grid.
+    fn heuristic(&self, (a_column, a_row): Cell, (b_column, b_row): Cell) -> f64 {
+        let dx = a_column.abs_diff(b_column) as f64;
+        let dy = a_row.abs_diff(b_row) as f64;
+        (dx.max(dy) + (std::f64::consts::SQRT_2 - 1.0) * dx.min(dy)) * self.cell_size
+    }
+
+    /// Cells next to `cell` with the distance to them. Diagonals are only
+    /// included when asked for, and never past the corner of a blocked cell.
+    fn neighbours(&self, (column, row): Cell, diagonals: bool) -> Vec<(Cell, f64)> {
+        let in_grid = |dx: isize, dy: isize| {
+            let column = column
+                .checked_add_signed(dx)
+                .filter(|&c| c < self.columns)?;
+            let row = row.checked_add_signed(dy).filter(|&r| r < self.rows)?;
+            Some((column, row))
+        };
+        let mut result = Vec::new();
+        for (dx, dy) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+            if let Some(neighbour) = in_grid(dx, dy) {
+                result.push((neighbour, self.cell_size));
+            }
+        }
+        if diagonals {
+            for (dx, dy) in [(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+                let corners_open = [in_grid(dx, 0), in_grid(0, dy)]
+                    .iter()
+                    .all(|corner| corner.is_some_and(|corner| !self.blocked_cell(corner)));
+                if let Some(neighbour) = in_grid(dx, dy).filter(|_| corners_open) {
+                    result.push((neighbour, self.cell_size * std::f64::consts::SQRT_2));
+                }
+            }
+        }
+        result
+    }
+
+    /// Keeps only the waypoints that can't be seen past, from the first
+    /// point onwards, and drops the first point.
+    fn smooth(&self, points: &[Vector2]) -> Vec<Vector2> {
+        let mut result = Vec::new();
+        let mut current = 0;
+        while current < points.len() - 1 {
+            let furthest = (current + 1..points.len())
+                .rev()
+                .find(|&next| self.is_walkable(points[current], points[next]))
+                .unwrap_or(current + 1);
+            result.push(points[furthest]);
+            current = furthest;
+        }
+        result
+    }
+
+    fn index(&self, (column, row): Cell) -> usize {
+        row * self.columns + column
+    }
+}
+
+/// Path being followed through a `NavGrid`, kept between steps.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Route {
+    waypoints: Vec<Vector2>,
+    /// Cell the target was in when the path was found.
+    goal: Option<Cell>,
+    /// Seconds since the path was found.
+    age: f64,
+}
+
+impl Route {
+    pub fn new() -> Self {
+        Route::default()
+    }
+
+    /// Forgets the path, so the next one is found from scratch.
+    pub fn clear(&mut self) {
+        *self = Route::new();
+    }
+}
+
+/// Open list entry, ordered so the heap pops the lowest estimate first.
+struct Node {
+    estimate: f64,
+    cell: Cell,
+}
+
+impl PartialEq for Node {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Node {}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .total_cmp(&self.estimate)
+            .then_with(|| other.cell.cmp(&self.cell))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collider::Collider;
+    use crate::navigation::{NavGrid, Route};
+    use crate::settings;
+    use crate::vector2::Vector2;
+
+    fn wall() -> Collider {
+        // Splits a 100 by 100 area from the top down to y = 80.
+        Collider::new_box(Vector2::new(50.0, 40.0), 5.0, 40.0, 0.0)
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_cell_size_less_than_equal_zero_panic() {
+        NavGrid::new(100.0, 100.0, 0.0, 1.0, &[]);
+    }
+
+    #[test]
+    fn new_cells_touching_obstacle_are_blocked() {
+        let grid = NavGrid::new(100.0, 100.0, 10.0, 1.0, &[wall()]);
+
+        assert!(grid.is_blocked(Vector2::new(50.0, 10.0)));
+        assert!(!grid.is_blocked(Vector2::new(20.0, 10.0)));
+        assert!(!grid.is_blocked(Vector2::new(50.0, 95.0)));
+    }
+
+    #[test]
+    fn find_path_without_obstacles_goes_straight_to_target() {
+        let grid = NavGrid::new(100.0, 100.0, 10.0, 1.0, &[]);
+
+        let result = grid
+            .find_path(Vector2::new(5.0, 5.0), Vector2::new(95.0, 55.0))
+            .unwrap();
+
+        assert_eq!(result, vec![Vector2::new(95.0, 55.0)]);
+    }
+
+    #[test]
+    fn find_path_around_wall_passes_below_it() {
+        let grid = NavGrid::new(100.0, 100.0, 10.0, 1.0, &[wall()]);
+        let from = Vector2::new(15.0, 15.0);
+        let to = Vector2::new(85.0, 15.0);
+
+        let result = grid.find_path(from, to).unwrap();
+
+        assert_eq!(*result.last().unwrap(), to);
+        assert!(result.iter().any(|point| point.y > 80.0));
+        let mut previous = from;
+        for &point in &result {
+            assert!(grid.is_walkable(previous, point));
+            previous = point;
+        }
+    }
+
+    #[test]
+    fn find_path_smoothing_leaves_few_waypoints() {
+        let grid = NavGrid::new(100.0, 100.0, 10.0, 1.0, &[wall()]);
+
+        let result = grid
+            .find_path(Vector2::new(15.0, 15.0), Vector2::new(85.0, 15.0))
+            .unwrap();
+
+        assert!(result.len() <= 3);
+    }
+
+    #[test]
+    fn find_path_to_walled_off_target_returns_none() {
+        let walls = [Collider::new_box(Vector2::new(50.0, 50.0), 5.0, 60.0, 0.0)];
+        let grid = NavGrid::new(100.0, 100.0, 10.0, 1.0, &walls);
+
+        let result = grid.find_path(Vector2::new(15.0, 15.0), Vector2::new(85.0, 15.0));
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn is_walkable_through_wall_returns_false() {
+        let grid = NavGrid::new(100.0, 100.0, 10.0, 1.0, &[wall()]);
+
+        assert!(!grid.is_walkable(Vector2::new(15.0, 15.0), Vector2::new(85.0, 15.0)));
+        assert!(grid.is_walkable(Vector2::new(15.0, 95.0), Vector2::new(85.0, 95.0)));
+    }
+
+    #[test]
+    fn follow_target_in_same_cell_keeps_path() {
+        let grid = NavGrid::new(100.0, 100.0, 10.0, 1.0, &[wall()]);
+        let mut route = Route::new();
+        let from = Vector2::new(15.0, 15.0);
+        grid.follow(&mut route, from, Vector2::new(85.0, 15.0), 0.01);
+        let sentinel = Vector2::new(1.0, 2.0);
+        route.waypoints.insert(0, sentinel);
+
+        let result = grid.follow(&mut route, from, Vector2::new(86.0, 16.0), 0.01);
+
+        assert_eq!(result, Some(sentinel));
+        assert_eq!(*route.waypoints.last().unwrap(), Vector2::new(86.0, 16.0));
+    }
+
+    #[test]
+    fn follow_target_in_new_cell_finds_new_path() {
+        let grid = NavGrid::new(100.0, 100.0, 10.0, 1.0, &[wall()]);
+        let mut route = Route::new();
+        let from = Vector2::new(15.0, 15.0);
+        grid.follow(&mut route, from, Vector2::new(85.0, 15.0), 0.01);
+        route.waypoints.insert(0, Vector2::new(1.0, 2.0));
+
+        let result = grid.follow(&mut route, from, Vector2::new(85.0, 45.0), 0.01);
+
+        assert_eq!(
+            result,
+            grid.find_path(from, Vector2::new(85.0, 45.0))
+                .map(|path| path[0])
+        );
+    }
+
+    #[test]
+    fn follow_old_path_finds_new_path() {
+        let grid = NavGrid::new(100.0, 100.0, 10.0, 1.0, &[wall()]);
+        let mut route = Route::new();
+        let from = Vector2::new(15.0, 15.0);
+        let to = Vector2::new(85.0, 15.0);
+        grid.follow(&mut route, from, to, 0.01);
+        let sentinel = Vector2::new(1.0, 2.0);
+        route.waypoints.insert(0, sentinel);
+
+        let result = grid.follow(&mut route, from, to, settings::navigation::REPLAN_INTERVAL);
+
+        assert_ne!(result, Some(sentinel));
+        assert_eq!(result, grid.find_path(from, to).map(|path| path[0]));
+    }
+
+    #[test]
+    fn follow_reached_waypoint_moves_on_to_next() {
+        let grid = NavGrid::new(100.0, 100.0, 10.0, 1.0, &[wall()]);
+        let mut route = Route::new();
+        let to = Vector2::new(85.0, 15.0);
+        let first = grid
+            .follow(&mut route, Vector2::new(15.0, 15.0), to, 0.01)
+            .unwrap();
+
+        let result = grid.follow(&mut route, first, to, 0.01).unwrap();
+
+        assert_ne!(result, first);
+    }
+}
@@ -0,0 +1,196 @@
+use crate::manifold::Manifold;
+use crate::vector2::Vector2;
+
+/// Dynamic state of a moving entity, integrated once per update.
+pub struct RigidBody {
+    pub velocity: Vector2,
+    pub acceleration: Vector2,
+    /// Share of the velocity lost per second, with no acceleration a body
+    /// slows to about `1 / (1 + drag)` of its speed after a second.
+    pub drag: f64,
+    mass: f64,
+    /// Share of the closing speed kept after a collision, `0.0` stops dead
+    /// and `1.0` bounces back perfectly.
+    pub restitution: f64,
+}
+
+impl RigidBody {
+    pub fn new(mass: f64, drag: f64, restitution: f64) -> Self {
+        if mass <= 0.0 {
+            panic!("Mass of rigid body must be greater than 0");
+        }
+        RigidBody {
+            velocity: Vector2::new(0.0, 0.0),
+            acceleration: Vector2::new(0.0, 0.0),
+            drag,
+            mass,
+            restitution,
+        }
+    }
+
+    pub fn get_mass(&self) -> f64 {
+        self.mass
+    }
+
+    pub fn set_mass(&mut self, mass: f64) {
+        if mass <= 0.0 {
+            panic!("Mass of rigid body must be greater than 0");
+        }
+        self.mass = mass;
+    }
+
+    pub fn apply_impulse(&mut self, impulse: Vector2) {
+        self.velocity = self.velocity + impulse / self.mass;
+    }
+
+    /// Advances the velocity by `dt` seconds and returns how far the body
+    /// moves in that time.
+    pub fn integrate(&mut self, dt: f64) -> Vector2 {
+        self.velocity = (self.velocity + self.acceleration * dt) / (1.0 + self.drag * dt);
+        self.velocity * dt
+    }
+
+    /// Bounces off an immovable surface, `normal` pointing from the body into
+    /// the surface.
+    pub fn bounce(&mut self, normal: Vector2) {
+        let closing_speed = self.velocity.dot(normal);
+        if closing_speed > 0.0 {
+            self.velocity = self.velocity - normal * closing_speed * (1.0 + self.restitution);
+        }
+    }
+}
+
+/// Exchanges momentum between two colliding bodies along the contact normal,
+/// `manifold` pointing from `a` towards `b`.
+pub fn resolve_collision(a: &mut RigidBody, b: &mut RigidBody, manifold: &Manifold) {
+    let closing_speed = (a.velocity - b.velocity).dot(manifold.normal);
+    if closing_speed <= 0.0 {
+        return;
+    }
+    let restitution = a.restitution.min(b.restitution);
+    let impulse = (1.0 + restitution) * closing_speed / (1.0 / a.mass + 1.0 / b.mass);
+    a.apply_impulse(-manifold.normal * impulse);
+    b.apply_impulse(manifold.normal * impulse);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::manifold::Manifold;
+    use crate::physics::{resolve_collision, RigidBody};
+    use crate::vector2::Vector2;
+
+    fn contact(normal: Vector2) -> Manifold {
+        Manifold {
+            normal,
+            depth: 1.0,
+            point: Vector2::new(0.0, 0.0),
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_mass_less_than_equal_zero_panic() {
+        RigidBody::new(0.0, 0.0, 0.0);
+    }
+
+    #[test]
+    fn integrate_constant_acceleration_accumulates_velocity() {
+        let mut body = RigidBody::new(1.0, 0.0, 0.0);
+        body.acceleration = Vector2::new(2.0, 0.0);
+
+        body.integrate(0.5);
+        let result = body.integrate(0.5);
+
+        assert_eq!(body.velocity, Vector2::new(2.0, 0.0));
+        assert_eq!(result, Vector2::new(1.0, 0.0));
+    }
+
+    #[test]
+    fn integrate_drag_slows_body_down() {
+        let mut body = RigidBody::new(1.0, 1.0, 0.0);
+        body.velocity = Vector2::new(10.0, 0.0);
+
+        body.integrate(1.0);
+
+        assert_eq!(body.velocity, Vector2::new(5.0, 0.0));
+    }
+
+    #[test]
+    fn integrate_drag_and_acceleration_approach_terminal_velocity() {
+        let mut body = RigidBody::new(1.0, 4.0, 0.0);
+        body.acceleration = Vector2::new(100.0, 0.0);
+
+        for _ in 0..1000 {
+            body.integrate(0.01);
+        }
+
+        assert!((body.velocity.x - 25.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn apply_impulse_heavier_body_changes_velocity_less() {
+        let mut body = RigidBody::new(4.0, 0.0, 0.0);
+
+        body.apply_impulse(Vector2::new(2.0, 0.0));
+
+        assert_eq!(body.velocity, Vector2::new(0.5, 0.0));
+    }
+
+    #[test]
+    fn bounce_into_surface_reflects_by_restitution() {
+        let mut body = RigidBody::new(1.0, 0.0, 0.5);
+        body.velocity = Vector2::new(3.0, 4.0);
+
+        body.bounce(Vector2::new(1.0, 0.0));
+
+        assert_eq!(body.velocity, Vector2::new(-1.5, 4.0));
+    }
+
+    #[test]
+    fn bounce_moving_away_from_surface_keeps_velocity() {
+        let mut body = RigidBody::new(1.0, 0.0, 0.5);
+        body.velocity = Vector2::new(-3.0, 4.0);
+
+        body.bounce(Vector2::new(1.0, 0.0));
+
+        assert_eq!(body.velocity, Vector2::new(-3.0, 4.0));
+    }
+
+    #[test]
+    fn resolve_collision_elastic_equal_masses_swap_velocities() {
+        let mut a = RigidBody::new(1.0, 0.0, 1.0);
+        let mut b = RigidBody::new(1.0, 0.0, 1.0);
+        a.velocity = Vector2::new(2.0, 0.0);
+
+        resolve_collision(&mut a, &mut b, &contact(Vector2::new(1.0, 0.0)));
+
+        assert_eq!(a.velocity, Vector2::new(0.0, 0.0));
+        assert_eq!(b.velocity, Vector2::new(2.0, 0.0));
+    }
+
+    #[test]
+    fn resolve_collision_conserves_momentum() {
+        let mut a = RigidBody::new(1.0, 0.0, 0.3);
+        let mut b = RigidBody::new(3.0, 0.0, 0.6);
+        a.velocity = Vector2::new(4.0, 1.0);
+        b.velocity = Vector2::new(-1.0, 0.0);
+
+        resolve_collision(&mut a, &mut b, &contact(Vector2::new(1.0, 0.0)));
+
+        let momentum = a.velocity * a.get_mass() + b.velocity * b.get_mass();
+        assert!((momentum - Vector2::new(1.0, 1.0)).magnitude() < 1e-12);
+        assert!(a.velocity.x < b.velocity.x);
+    }
+
+    #[test]
+    fn resolve_collision_separating_bodies_unchanged() {
+        let mut a = RigidBody::new(1.0, 0.0, 1.0);
+        let mut b = RigidBody::new(1.0, 0.0, 1.0);
+        a.velocity = Vector2::new(-2.0, 0.0);
+
+        resolve_collision(&mut a, &mut b, &contact(Vector2::new(1.0, 0.0)));
+
+        assert_eq!(a.velocity, Vector2::new(-2.0, 0.0));
+        assert_eq!(b.velocity, Vector2::new(0.0, 0.0));
+    }
+}
@@ -1,21 +1,8 @@
-extern crate piston_window;
-
-use crate::collider::Collider;
-use crate::collides::Collides;
+use crate::input::KeyState;
 use crate::player::Action::NoMove;
 use crate::player::State::Active;
-use crate::position::Position;
 use crate::settings;
-use crate::settings::player;
 use crate::vector2::Vector2;
-use opengl_graphics::GlGraphics;
-use piston_window::{ellipse, Context};
-
-#[derive(Clone, Copy)]
-pub enum KeyState {
-    Pressed,
-    NotPressed,
-}
 
 enum Action {
     NoMove,
@@ -23,73 +10,108 @@ enum Action {
     Minus,
 }
 
-#[allow(dead_code)]
 enum State {
     Active(Action),
     Dead,
 }
 
+/// Marks the entity as steered by the keyboard.
 pub struct Player {
-    pub collider: Collider,
     horizontal: State,
     vertical: State,
-    pub(crate) color: [f32; 4],
+    lives: u32,
+    /// Seconds until a dead player comes back, `None` when alive or out of
+    /// lives.
+    respawn_in: Option<f64>,
 }
 
-impl Collides for Player {
-    fn get_collider(&mut self) -> &mut Collider {
-        &mut self.collider
+impl Default for Player {
+    fn default() -> Self {
+        Player::new()
     }
 }
 
-impl Position for Player {
-    fn set_position(&mut self, position: Vector2) {
-        self.get_collider().set_position(position)
-    }
-
-    fn get_position(&mut self) -> Vector2 {
-        self.get_collider().get_position()
+impl Player {
+    pub fn new() -> Self {
+        Player::with_lives(settings::player::LIVES)
     }
-}
 
-impl Player {
-    pub fn new(position: Vector2) -> Self {
+    pub fn with_lives(lives: u32) -> Self {
+        if lives == 0 {
+            panic!("Player must start with at least one life");
+        }
         Player {
-            collider: Collider::new(position, settings::player::SIZE),
             horizontal: Active(NoMove),
             vertical: Active(NoMove),
-            color: settings::color::WHITE,
+            lives,
+            respawn_in: None,
+        }
+    }
+
+    /// Lives left, counting the current one while alive.
+    pub fn get_lives(&self) -> u32 {
+        self.lives
+    }
+
+    /// Loses a life and stops responding to input. Comes back after
+    /// `respawn_time` seconds if there are lives left.
+    pub fn die(&mut self, respawn_time: f64) {
+        if !self.is_alive() {
+            return;
+        }
+        self.horizontal = State::Dead;
+        self.vertical = State::Dead;
+        self.lives -= 1;
+        if self.lives > 0 {
+            self.respawn_in = Some(respawn_time);
+        }
+    }
+
+    /// Counts down to respawning, returns true on the update the player
+    /// comes back to life.
+    pub fn update(&mut self, dt: f64) -> bool {
+        let Some(respawn_in) = self.respawn_in else {
+            return false;
+        };
+        if respawn_in > dt {
+            self.respawn_in = Some(respawn_in - dt);
+            return false;
         }
+        self.respawn_in = None;
+        self.horizontal = Active(NoMove);
+        self.vertical = Active(NoMove);
+        true
     }
 
-    pub fn update(&mut self, dt: f64) {
-        let mut target = Vector2::new(0.0, 0.0);
-        let current_position = self.get_position();
+    /// Dead with no lives left to come back with.
+    pub fn is_out(&self) -> bool {
+        !self.is_alive() && self.lives == 0
+    }
+
+    /// Unit direction the held keys point in, zero when standing still.
+    #[allow(clippy::single_match)]
+    pub fn direction(&self) -> Vector2 {
+        let mut direction = Vector2::new(0.0, 0.0);
         match &self.horizontal {
             State::Active(action) => match action {
-                Action::Minus => target.x = current_position.x - player::SPEED * dt,
-                Action::Plus => target.x = current_position.x + player::SPEED * dt,
-                Action::NoMove => target.x = current_position.x,
+                Action::Minus => direction.x = -1.0,
+                Action::Plus => direction.x = 1.0,
+                Action::NoMove => {}
             },
             _ => {}
         }
         match &self.vertical {
             State::Active(action) => match action {
-                Action::Minus => target.y = current_position.y + player::SPEED * dt,
-                Action::Plus => target.y = current_position.y - player::SPEED * dt,
-                Action::NoMove => target.y = current_position.y,
+                Action::Minus => direction.y = 1.0,
+                Action::Plus => direction.y = -1.0,
+                Action::NoMove => {}
             },
             _ => {}
         }
-        if current_position != target {
-            self.set_position(Vector2::move_towards(
-                current_position,
-                target,
-                settings::player::SPEED * dt,
-            ));
-        }
+        direction.normalized()
     }
 
+    #[allow(clippy::single_match)]
     pub fn input(&mut self, left: KeyState, right: KeyState, up: KeyState, down: KeyState) {
         match self.horizontal {
             State::Active(_) => match (left, right) {
@@ -117,34 +139,65 @@ impl Player {
         }
     }
 
-    pub fn draw(&mut self, c: Context, g: &mut GlGraphics) {
-        match &self.horizontal {
-            State::Active(_action) => {
-                let position = self.get_position();
-                // let square = rectangle::square(0.0, 0.0, player::SIZE);
-                //
-                // let (x, y) = (self.collider.position.x, self.collider.position.y);
-                // let transform = c.transform.trans(x, y);
-                //
-                // rectangle(color::WHITE, square, transform, g);
-                let rect = [
-                    position.x - player::SIZE,
-                    position.y - player::SIZE,
-                    player::SIZE * 2.0,
-                    player::SIZE * 2.0,
-                ];
-
-                ellipse(self.color, rect, c.transform, g);
-            }
-            State::Dead => {}
+    pub fn is_alive(&self) -> bool {
+        match self.horizontal {
+            State::Active(_) => true,
+            State::Dead => false,
         }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::KeyState;
+    use crate::player::Player;
+    use crate::vector2::Vector2;
 
-    pub fn damage(&mut self) {
-        self.color[3] = 0.25
+    #[test]
+    #[should_panic]
+    fn with_lives_zero_panic() {
+        Player::with_lives(0);
     }
 
-    pub fn normal(&mut self) {
-        self.color[3] = 1.0
+    #[test]
+    fn die_loses_life_and_ignores_input() {
+        let mut player = Player::with_lives(2);
+
+        player.die(1.0);
+        player.input(
+            KeyState::NotPressed,
+            KeyState::Pressed,
+            KeyState::NotPressed,
+            KeyState::NotPressed,
+        );
+
+        assert!(!player.is_alive());
+        assert_eq!(player.get_lives(), 1);
+        assert_eq!(player.direction(), Vector2::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn update_after_respawn_time_brings_player_back() {
+        let mut player = Player::with_lives(2);
+        player.die(1.0);
+
+        let early = player.update(0.5);
+        let on_time = player.update(0.5);
+
+        assert!(!early);
+        assert!(on_time);
+        assert!(player.is_alive());
+        assert_eq!(player.get_lives(), 1);
+    }
+
+    #[test]
+    fn die_on_last_life_never_comes_back() {
+        let mut player = Player::with_lives(1);
+
+        player.die(1.0);
+        let result = player.update(10.0);
+
+        assert!(!result);
+        assert!(player.is_out());
     }
 }
@@ -1,3 +1,4 @@
+use crate::collider::Collider;
 use crate::vector2::Vector2;
 
 pub trait Position {
@@ -5,3 +6,13 @@ pub trait Position {
 
     fn get_position(&mut self) -> Vector2;
 }
+
+impl Position for Collider {
+    fn set_position(&mut self, position: Vector2) {
+        Collider::set_position(self, position)
+    }
+
+    fn get_position(&mut self) -> Vector2 {
+        Collider::get_position(self)
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::BTreeMap;
+
+use crate::collider::Proximity;
+
+/// Two colliders that came within the margin of each other and left again
+/// without touching, smaller id first.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NearMiss<T = usize> {
+    pub a: T,
+    pub b: T,
+    /// Smallest gap between them while they were near.
+    pub closest: f64,
+}
+
+/// Follows pairs of colliders through their passes near each other to turn
+/// per-frame proximity into near misses.
+pub struct ProximityTracker<T = usize> {
+    /// Closest approach of every pair within the margin, `None` once it
+    /// touched.
+    close: BTreeMap<(T, T), Option<f64>>,
+}
+
+impl<T: Copy + Ord> Default for ProximityTracker<T> {
+    fn default() -> Self {
+        ProximityTracker::new()
+    }
+}
+
+impl<T: Copy + Ord> ProximityTracker<T> {
+    pub fn new() -> Self {
+        ProximityTracker {
+            close: BTreeMap::new(),
+        }
+    }
+
+    /// Takes this frame's proximity of every pair and returns the near
+    /// misses of pairs that left the margin, in pair order. A pair left out
+    /// counts as clear.
+    pub fn update(&mut self, pairs: &[(T, T, Proximity)]) -> Vec<NearMiss<T>> {
+        let mut close = BTreeMap::new();
+        for &(a, b, proximity) in pairs {
+            let pair = (a.min(b), a.max(b));
+            let closest = self.close.get(&pair).copied();
+            match proximity {
+                Proximity::Touching => {
+                    close.insert(pair, None);
+                }
+                Proximity::Near(distance) => {
+                    let closest = closest.unwrap_or(Some(distance));
+                    close.insert(pair, closest.map(|closest| closest.min(distance)));
+                }
+                Proximity::Clear => {}
+            }
+        }
+
+        let near_misses = self
+            .close
+            .iter()
+            .filter(|(pair, _)| !close.contains_key(*pair))
+            .filter_map(|(&(a, b), &closest)| {
+                Some(NearMiss {
+                    a,
+                    b,
+                    closest: closest?,
+                })
+            })
+            .collect();
+        self.close = close;
+        near_misses
+    }
+
+    /// Forgets a collider that went away, without any near misses.
+    pub fn remove(&mut self, id: T) {
+        self.close.retain(|&(a, b), _| a != id && b != id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collider::Proximity;
+    use crate::proximity::{NearMiss, ProximityTracker};
+
+    #[test]
+    fn update_pair_passing_by_returns_near_miss_with_closest_approach() {
+        let mut tracker: ProximityTracker = ProximityTracker::new();
+
+        tracker.update(&[(0, 1, Proximity::Near(8.0))]);
+        tracker.update(&[(0, 1, Proximity::Near(3.0))]);
+        tracker.update(&[(0, 1, Proximity::Near(6.0))]);
+        let result = tracker.update(&[(0, 1, Proximity::Clear)]);
+
+        assert_eq!(
+            result,
+            vec![NearMiss {
+                a: 0,
+                b: 1,
+                closest: 3.0
+            }]
+        );
+    }
+
+    #[test]
+    fn update_pair_still_near_returns_nothing() {
+        let mut tracker: ProximityTracker = ProximityTracker::new();
+
+        tracker.update(&[(0, 1, Proximity::Near(8.0))]);
+        let result = tracker.update(&[(0, 1, Proximity::Near(3.0))]);
+
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn update_pair_that_touched_returns_nothing() {
+        let mut tracker: ProximityTracker = ProximityTracker::new();
+
+        tracker.update(&[(0, 1, Proximity::Near(8.0))]);
+        tracker.update(&[(0, 1, Proximity::Touching)]);
+        tracker.update(&[(0, 1, Proximity::Near(8.0))]);
+        let result = tracker.update(&[]);
+
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn update_left_out_pair_counts_as_clear() {
+        let mut tracker: ProximityTracker = ProximityTracker::new();
+
+        tracker.update(&[(1, 0, Proximity::Near(5.0))]);
+        let result = tracker.update(&[]);
+
+        assert_eq!(
+            result,
+            vec![NearMiss {
+                a: 0,
+                b: 1,
+                closest: 5.0
+            }]
+        );
+    }
+
+    #[test]
+    fn update_second_pass_after_near_miss_counts_again() {
+        let mut tracker: ProximityTracker = ProximityTracker::new();
+
+        tracker.update(&[(0, 1, Proximity::Near(5.0))]);
+        tracker.update(&[]);
+        tracker.update(&[(0, 1, Proximity::Near(7.0))]);
+        let result = tracker.update(&[]);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].closest, 7.0);
+    }
+
+    #[test]
+    fn remove_near_collider_forgets_it_without_near_miss() {
+        let mut tracker: ProximityTracker = ProximityTracker::new();
+
+        tracker.update(&[(0, 1, Proximity::Near(5.0)), (2, 3, Proximity::Near(5.0))]);
+        tracker.remove(1);
+        let result = tracker.update(&[]);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!((result[0].a, result[0].b), (2, 3));
+    }
+}
@@ -0,0 +1,184 @@
+use crate::collider::Collider;
+use crate::layer;
+use crate::vector2::Vector2;
+
+/// Half-line from `origin` along the unit `direction`, cut off after
+/// `max_distance`. Only colliders on a layer in `mask` are hit.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ray {
+    pub origin: Vector2,
+    pub direction: Vector2,
+    pub max_distance: f64,
+    pub mask: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RayHit {
+    /// Distance from the ray origin to the hit.
+    pub distance: f64,
+    /// Unit surface normal at the hit, facing the ray.
+    pub normal: Vector2,
+    pub point: Vector2,
+}
+
+impl Ray {
+    pub fn new(origin: Vector2, direction: Vector2, max_distance: f64) -> Self {
+        if direction.magnitude() == 0.0 {
+            panic!("Direction of ray must not be zero");
+        }
+        Ray {
+            origin,
+            direction: direction.normalized(),
+            max_distance,
+            mask: layer::ALL,
+        }
+    }
+
+    /// Segment from `from` to `to`, panics if they are the same point.
+    pub fn between(from: Vector2, to: Vector2) -> Self {
+        Ray::new(from, to - from, (to - from).magnitude())
+    }
+
+    pub fn point_at(&self, distance: f64) -> Vector2 {
+        self.origin + self.direction * distance
+    }
+
+    /// Closest collider the ray hits, with its index in `colliders`.
+    pub fn closest_hit(&self, colliders: &[&Collider]) -> Option<(usize, RayHit)> {
+        colliders
+            .iter()
+            .enumerate()
+            .filter_map(|(i, collider)| collider.raycast(self).map(|hit| (i, hit)))
+            .min_by(|(_, a), (_, b)| a.distance.partial_cmp(&b.distance).unwrap())
+    }
+}
+
+/// Whether nothing in `obstacles` blocks the segment between the points.
+/// An obstacle `from` lies inside doesn't count, as rays starting inside a
+/// collider don't hit it.
+pub fn line_of_sight(from: Vector2, to: Vector2, obstacles: &[&Collider]) -> bool {
+    if from == to {
+        return true;
+    }
+    let ray = Ray::between(from, to);
+    obstacles
+        .iter()
+        .all(|obstacle| obstacle.raycast(&ray).is_none())
+}
+
+/// Whether a circle of `radius` can move straight between the points
+/// without anything in `obstacles` in the way. The middle and both edges of
+/// the strip it sweeps are checked, which is enough for obstacles no
+/// thinner than the circle. Like `line_of_sight`, obstacles an edge of the
+/// strip starts inside don't count, so a circle sunk a little into a wall
+/// can still slide along or away from it.
+pub fn clear_path(from: Vector2, to: Vector2, radius: f64, obstacles: &[&Collider]) -> bool {
+    if from == to {
+        return true;
+    }
+    let side = (to - from).normalized().perpendicular() * radius;
+    [Vector2::new(0.0, 0.0), side, -side]
+        .iter()
+        .all(|&offset| line_of_sight(from + offset, to + offset, obstacles))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collider::Collider;
+    use crate::ray::{clear_path, line_of_sight, Ray};
+    use crate::vector2::Vector2;
+
+    #[test]
+    #[should_panic]
+    fn new_zero_direction_panic() {
+        Ray::new(Vector2::new(0.0, 0.0), Vector2::new(0.0, 0.0), 1.0);
+    }
+
+    #[test]
+    fn between_points_normalizes_direction_and_sets_length() {
+        let ray = Ray::between(Vector2::new(1.0, 1.0), Vector2::new(4.0, 5.0));
+
+        assert_eq!(ray.direction, Vector2::new(0.6, 0.8));
+        assert_eq!(ray.max_distance, 5.0);
+        assert_eq!(ray.point_at(5.0), Vector2::new(4.0, 5.0));
+    }
+
+    #[test]
+    fn closest_hit_returns_nearest_collider() {
+        let far = Collider::new(Vector2::new(10.0, 0.0), 1.0);
+        let near = Collider::new(Vector2::new(5.0, 0.0), 1.0);
+        let ray = Ray::new(Vector2::new(0.0, 0.0), Vector2::new(1.0, 0.0), 100.0);
+
+        let (index, hit) = ray.closest_hit(&[&far, &near]).unwrap();
+
+        assert_eq!(index, 1);
+        assert_eq!(hit.distance, 4.0);
+    }
+
+    #[test]
+    fn line_of_sight_blocked_by_wall_returns_false() {
+        let wall = Collider::new_box(Vector2::new(5.0, 0.0), 0.5, 10.0, 0.0);
+
+        let result = line_of_sight(Vector2::new(0.0, 0.0), Vector2::new(10.0, 0.0), &[&wall]);
+
+        assert!(!result);
+    }
+
+    #[test]
+    fn line_of_sight_wall_beyond_target_returns_true() {
+        let wall = Collider::new_box(Vector2::new(15.0, 0.0), 0.5, 10.0, 0.0);
+
+        let result = line_of_sight(Vector2::new(0.0, 0.0), Vector2::new(10.0, 0.0), &[&wall]);
+
+        assert!(result);
+    }
+
+    #[test]
+    fn clear_path_corner_within_radius_returns_false() {
+        let wall = Collider::new_box(Vector2::new(50.0, 0.0), 10.0, 10.0, 0.0);
+        let from = Vector2::new(0.0, 15.0);
+        let to = Vector2::new(100.0, 15.0);
+
+        let result = clear_path(from, to, 10.0, &[&wall]);
+
+        assert!(line_of_sight(from, to, &[&wall]));
+        assert!(!result);
+    }
+
+    #[test]
+    fn clear_path_sliding_along_wall_returns_true() {
+        let wall = Collider::new_box(Vector2::new(50.0, -10.0), 100.0, 10.0, 0.0);
+
+        let result = clear_path(
+            Vector2::new(0.0, 10.0),
+            Vector2::new(100.0, 10.0),
+            10.0,
+            &[&wall],
+        );
+
+        assert!(result);
+    }
+
+    #[test]
+    fn line_of_sight_from_inside_wall_returns_true() {
+        let wall = Collider::new_box(Vector2::new(0.0, 0.0), 10.0, 10.0, 0.0);
+
+        let result = line_of_sight(Vector2::new(9.0, 0.0), Vector2::new(100.0, 0.0), &[&wall]);
+
+        assert!(result);
+    }
+
+    #[test]
+    fn clear_path_sunk_into_wall_returns_true() {
+        let wall = Collider::new_box(Vector2::new(50.0, -10.0), 100.0, 10.0, 0.0);
+
+        let result = clear_path(
+            Vector2::new(0.0, 9.0),
+            Vector2::new(100.0, 9.0),
+            10.0,
+            &[&wall],
+        );
+
+        assert!(result);
+    }
+}
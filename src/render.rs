@@ -0,0 +1,120 @@
+//! Piston front-end drawing of the world.
+
+use dodge::collider::{Collider, ColliderKind, Shape};
+use dodge::hud::{Align, Label};
+use dodge::scene::{Game, Scene};
+use dodge::settings::color;
+use dodge::{Vector2, World};
+use graphics::{clear, CharacterCache, Text, Transformed};
+use opengl_graphics::{GlGraphics, GlyphCache};
+use piston_window::{ellipse, polygon, rectangle, Context};
+
+pub fn draw_game(game: &Game, c: Context, g: &mut GlGraphics) {
+    let colors = &game.settings().color;
+    match game.scene() {
+        Scene::Title => clear(colors.background, g),
+        Scene::Playing => draw_world(game.world(), c, g),
+        Scene::Paused => {
+            draw_world(game.world(), c, g);
+            draw_overlay(colors.paused, c, g);
+        }
+        Scene::GameOver | Scene::EnterName => {
+            draw_world(game.world(), c, g);
+            draw_overlay(colors.game_over, c, g);
+        }
+    }
+}
+
+pub fn draw_world(world: &World, c: Context, g: &mut GlGraphics) {
+    let colors = &world.settings().color;
+    // Clear the screen.
+    clear(colors.background, g);
+
+    for wall in world.arena().walls() {
+        draw_polygon(colors.obstacle, &wall.world_vertices(), c, g);
+    }
+
+    for (entity, sprite) in world.sprites.iter() {
+        let dead_player = world
+            .players
+            .get(entity)
+            .is_some_and(|player| !player.is_alive());
+        if dead_player {
+            continue;
+        }
+        if let Some(position) = world.interpolated_position(entity) {
+            draw_circle(sprite.color, position, sprite.radius, c, g);
+        }
+    }
+}
+
+/// Draws the HUD labels in `color` over whatever is on screen.
+pub fn draw_hud(
+    labels: &[Label],
+    color: [f32; 4],
+    glyphs: &mut GlyphCache,
+    c: Context,
+    g: &mut GlGraphics,
+) {
+    for label in labels {
+        let width = glyphs.width(label.size, &label.text).unwrap_or(0.0);
+        let x = match label.align {
+            Align::Left => label.position.x,
+            Align::Center => label.position.x - width / 2.0,
+            Align::Right => label.position.x - width,
+        };
+        let transform = c.transform.trans(x.round(), label.position.y.round());
+        Text::new_color(color, label.size)
+            .draw(&label.text, glyphs, &c.draw_state, transform, g)
+            .ok();
+    }
+}
+
+/// Outlines every collider in the world over the top of it.
+pub fn draw_debug(world: &World, c: Context, g: &mut GlGraphics) {
+    for wall in world.arena().walls() {
+        draw_collider_debug(wall, c, g);
+    }
+    for (_, collider) in world.colliders.iter() {
+        draw_collider_debug(collider, c, g);
+    }
+}
+
+pub fn draw_collider_debug(collider: &Collider, c: Context, g: &mut GlGraphics) {
+    if !collider.is_enabled() {
+        return;
+    }
+    let debug_color = match collider.get_kind() {
+        ColliderKind::Solid => color::DEBUG,
+        ColliderKind::Trigger => color::DEBUG_TRIGGER,
+    };
+    match collider.get_shape() {
+        Shape::Circle { radius } => {
+            draw_circle(debug_color, collider.get_position(), *radius, c, g);
+        }
+        Shape::Polygon { .. } => {
+            draw_polygon(debug_color, &collider.world_vertices(), c, g);
+        }
+    }
+}
+
+fn draw_circle(color: [f32; 4], center: Vector2, radius: f64, c: Context, g: &mut GlGraphics) {
+    let rect = [
+        center.x - radius,
+        center.y - radius,
+        radius * 2.0,
+        radius * 2.0,
+    ];
+    ellipse(color, rect, c.transform, g);
+}
+
+fn draw_polygon(color: [f32; 4], vertices: &[Vector2], c: Context, g: &mut GlGraphics) {
+    let points: Vec<[f64; 2]> = vertices.iter().map(|vertex| [vertex.x, vertex.y]).collect();
+    polygon(color, &points, c.transform, g);
+}
+
+/// Tints the whole window.
+fn draw_overlay(color: [f32; 4], c: Context, g: &mut GlGraphics) {
+    let [width, height] = c.get_view_size();
+    rectangle(color, [0.0, 0.0, width, height], c.transform, g);
+}
@@ -0,0 +1,342 @@
+//! Separating Axis Theorem tests for circles and convex polygons.
+//!
+//! Polygons are given as world-space vertices in winding order. Two convex
+//! shapes are disjoint if and only if there is an axis on which their
+//! projections do not overlap, and for polygons it is enough to try the edge
+//! normals of both shapes. When every axis overlaps, the axis with the
+//! smallest overlap gives the contact normal and penetration depth, the
+//! normal facing whichever way the second shape has less far to go to get
+//! clear of the first.
+
+use crate::manifold::Manifold;
+use crate::vector2::Vector2;
+
+pub(crate) fn circles_contact(
+    a: Vector2,
+    a_radius: f64,
+    b: Vector2,
+    b_radius: f64,
+) -> Option<Manifold> {
+    let min_distance = a_radius + b_radius;
+    let difference = b - a;
+    let distance = difference.magnitude();
+    if distance >= min_distance {
+        return None;
+    }
+
+    // Concentric circles have no preferred direction, pick one.
+    let normal = if distance == 0.0 {
+        Vector2::new(1.0, 0.0)
+    } else {
+        difference / distance
+    };
+    let depth = min_distance - distance;
+    Some(Manifold {
+        normal,
+        depth,
+        point: a + normal * (a_radius - depth / 2.0),
+    })
+}
+
+pub(crate) fn polygons_contact(a: &[Vector2], b: &[Vector2]) -> Option<Manifold> {
+    let mut best: Option<(Vector2, f64)> = None;
+    for axis in axes(a).chain(axes(b)) {
+        let push = push_out(axis, project_polygon(a, axis), project_polygon(b, axis))?;
+        if best.is_none_or(|(_, depth)| push.1 < depth) {
+            best = Some(push);
+        }
+    }
+    let (normal, depth) = best?;
+
+    let contained: Vec<Vector2> = a
+        .iter()
+        .filter(|&&vertex| contains(b, vertex))
+        .chain(b.iter().filter(|&&vertex| contains(a, vertex)))
+        .copied()
+        .collect();
+    let point = if contained.is_empty() {
+        (centroid(a) + centroid(b)) / 2.0
+    } else {
+        centroid(&contained)
+    };
+    Some(Manifold {
+        normal,
+        depth,
+        point,
+    })
+}
+
+/// Contact from the circle towards the polygon.
+pub(crate) fn circle_polygon_contact(
+    center: Vector2,
+    radius: f64,
+    polygon: &[Vector2],
+) -> Option<Manifold> {
+    let closest_vertex = polygon
+        .iter()
+        .copied()
+        .min_by(|a, b| {
+            let a_distance = (*a - center).magnitude();
+            let b_distance = (*b - center).magnitude();
+            a_distance.partial_cmp(&b_distance).unwrap()
+        })
+        .unwrap();
+    // A center sitting exactly on the vertex gives no axis, the edges decide.
+    let vertex_axis =
+        Some((closest_vertex - center).normalized()).filter(|axis| axis.magnitude() > 0.0);
+
+    let mut best: Option<(Vector2, f64)> = None;
+    for axis in axes(polygon).chain(vertex_axis) {
+        let center_projection = center.dot(axis);
+        let circle_projection = (center_projection - radius, center_projection + radius);
+        let push = push_out(axis, circle_projection, project_polygon(polygon, axis))?;
+        if best.is_none_or(|(_, depth)| push.1 < depth) {
+            best = Some(push);
+        }
+    }
+    let (normal, depth) = best?;
+
+    Some(Manifold {
+        normal,
+        depth,
+        point: center + normal * (radius - depth / 2.0),
+    })
+}
+
+/// Gap between the surfaces of a circle and a polygon, zero if they touch.
+pub(crate) fn circle_polygon_distance(center: Vector2, radius: f64, polygon: &[Vector2]) -> f64 {
+    if contains(polygon, center) {
+        return 0.0;
+    }
+    (distance_to_edges(polygon, center) - radius).max(0.0)
+}
+
+/// Gap between two polygons, zero if they touch.
+pub(crate) fn polygons_distance(a: &[Vector2], b: &[Vector2]) -> f64 {
+    if polygons_contact(a, b).is_some() {
+        return 0.0;
+    }
+    let a_to_b = a.iter().map(|&vertex| distance_to_edges(b, vertex));
+    let b_to_a = b.iter().map(|&vertex| distance_to_edges(a, vertex));
+    a_to_b.chain(b_to_a).fold(f64::INFINITY, f64::min)
+}
+
+/// Distance from the point to the nearest point on the polygon's outline.
+fn distance_to_edges(polygon: &[Vector2], point: Vector2) -> f64 {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(&start, &end)| {
+            let edge = end - start;
+            let t = ((point - start).dot(edge) / edge.dot(edge)).clamp(0.0, 1.0);
+            (point - (start + edge * t)).magnitude()
+        })
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// Unit edge normals of the polygon.
+pub(crate) fn axes(polygon: &[Vector2]) -> impl Iterator<Item = Vector2> + '_ {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(&start, &end)| (end - start).perpendicular().normalized())
+}
+
+pub(crate) fn project_polygon(polygon: &[Vector2], axis: Vector2) -> (f64, f64) {
+    polygon.iter().map(|vertex| vertex.dot(axis)).fold(
+        (f64::INFINITY, f64::NEG_INFINITY),
+        |(min, max), projection| (min.min(projection), max.max(projection)),
+    )
+}
+
+/// Distance one projection has to move to stop overlapping the other.
+fn overlap((a_min, a_max): (f64, f64), (b_min, b_max): (f64, f64)) -> Option<f64> {
+    if a_min < b_max && b_min < a_max {
+        Some((a_max - b_min).min(b_max - a_min))
+    } else {
+        None
+    }
+}
+
+/// Shortest way out of an overlap along `axis`: the direction the second
+/// projection moves to get clear of the first, and how far.
+fn push_out(axis: Vector2, a: (f64, f64), b: (f64, f64)) -> Option<(Vector2, f64)> {
+    overlap(a, b)?;
+    let forward = a.1 - b.0;
+    let backward = b.1 - a.0;
+    Some(if forward <= backward {
+        (axis, forward)
+    } else {
+        (-axis, backward)
+    })
+}
+
+fn centroid(points: &[Vector2]) -> Vector2 {
+    points
+        .iter()
+        .fold(Vector2::new(0.0, 0.0), |sum, &point| sum + point)
+        / points.len() as f64
+}
+
+/// Whether the point lies inside a counter-clockwise convex polygon.
+fn contains(polygon: &[Vector2], point: Vector2) -> bool {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .all(|(&start, &end)| (end - start).cross(point - start) >= 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sat::{
+        circle_polygon_contact, circle_polygon_distance, circles_contact, polygons_contact,
+        polygons_distance,
+    };
+    use crate::vector2::Vector2;
+
+    fn square(x: f64, y: f64, half_size: f64) -> Vec<Vector2> {
+        vec![
+            Vector2::new(x - half_size, y - half_size),
+            Vector2::new(x + half_size, y - half_size),
+            Vector2::new(x + half_size, y + half_size),
+            Vector2::new(x - half_size, y + half_size),
+        ]
+    }
+
+    #[test]
+    fn circles_contact_touching_circles_returns_none() {
+        let result = circles_contact(Vector2::new(0.0, 0.0), 1.0, Vector2::new(2.0, 0.0), 1.0);
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn circles_contact_overlapping_circles_returns_depth_and_normal() {
+        let result =
+            circles_contact(Vector2::new(0.0, 0.0), 1.0, Vector2::new(1.5, 0.0), 1.0).unwrap();
+
+        assert_eq!(result.normal, Vector2::new(1.0, 0.0));
+        assert_eq!(result.depth, 0.5);
+        assert_eq!(result.point, Vector2::new(0.75, 0.0));
+    }
+
+    #[test]
+    fn circles_contact_concentric_circles_returns_full_depth() {
+        let result =
+            circles_contact(Vector2::new(0.0, 0.0), 1.0, Vector2::new(0.0, 0.0), 2.0).unwrap();
+
+        assert_eq!(result.depth, 3.0);
+        assert_eq!(result.normal.magnitude(), 1.0);
+    }
+
+    #[test]
+    fn polygons_contact_overlapping_squares_returns_shallowest_axis() {
+        let result = polygons_contact(&square(0.0, 0.0, 1.0), &square(1.5, 0.5, 1.0)).unwrap();
+
+        assert_eq!(result.normal, Vector2::new(1.0, 0.0));
+        assert_eq!(result.depth, 0.5);
+    }
+
+    #[test]
+    fn polygons_contact_normal_points_from_first_to_second() {
+        let result = polygons_contact(&square(1.5, 0.5, 1.0), &square(0.0, 0.0, 1.0)).unwrap();
+
+        assert_eq!(result.normal, Vector2::new(-1.0, 0.0));
+    }
+
+    #[test]
+    fn polygons_contact_point_lies_in_overlap() {
+        let result = polygons_contact(&square(0.0, 0.0, 1.0), &square(1.5, 1.5, 1.0)).unwrap();
+
+        assert_eq!(result.point, Vector2::new(0.75, 0.75));
+    }
+
+    #[test]
+    fn polygons_contact_separated_squares_returns_none() {
+        let result = polygons_contact(&square(0.0, 0.0, 1.0), &square(3.0, 0.0, 1.0));
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn polygons_contact_separated_by_triangle_edge_returns_none() {
+        // Bounding boxes overlap, but the hypotenuse separates the shapes.
+        let triangle = vec![
+            Vector2::new(0.0, 0.0),
+            Vector2::new(4.0, 0.0),
+            Vector2::new(0.0, 4.0),
+        ];
+
+        let result = polygons_contact(&triangle, &square(3.5, 3.5, 1.0));
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn circle_polygon_contact_circle_inside_polygon_returns_some() {
+        let result = circle_polygon_contact(Vector2::new(0.0, 0.0), 0.5, &square(0.0, 0.0, 2.0));
+
+        assert!(result.is_some());
+    }
+
+    #[test]
+    fn circle_polygon_contact_circle_near_corner_returns_none() {
+        // Within reach of both edges' extents but not of the corner itself.
+        let result = circle_polygon_contact(Vector2::new(1.8, 1.8), 1.0, &square(0.0, 0.0, 1.0));
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn circle_polygon_contact_circle_touching_edge_returns_normal_towards_polygon() {
+        let result =
+            circle_polygon_contact(Vector2::new(1.9, 0.0), 1.0, &square(0.0, 0.0, 1.0)).unwrap();
+
+        assert_eq!(result.normal, Vector2::new(-1.0, 0.0));
+        assert!((result.depth - 0.1).abs() < 1e-12);
+    }
+
+    #[test]
+    fn circle_polygon_contact_circle_past_corner_returns_corner_depth() {
+        let result =
+            circle_polygon_contact(Vector2::new(1.5, 1.5), 1.0, &square(0.0, 0.0, 1.0)).unwrap();
+
+        let expected_depth = 1.0 - 0.5 * std::f64::consts::SQRT_2;
+        assert!((result.depth - expected_depth).abs() < 1e-12);
+    }
+
+    #[test]
+    fn circle_polygon_distance_circle_beside_edge_returns_gap() {
+        let result = circle_polygon_distance(Vector2::new(5.0, 0.0), 1.0, &square(0.0, 0.0, 1.0));
+
+        assert_eq!(result, 3.0);
+    }
+
+    #[test]
+    fn circle_polygon_distance_circle_past_corner_measures_to_corner() {
+        let result = circle_polygon_distance(Vector2::new(4.0, 5.0), 1.0, &square(0.0, 0.0, 1.0));
+
+        assert!((result - 4.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn circle_polygon_distance_center_inside_returns_zero() {
+        let result = circle_polygon_distance(Vector2::new(0.5, 0.0), 0.1, &square(0.0, 0.0, 1.0));
+
+        assert_eq!(result, 0.0);
+    }
+
+    #[test]
+    fn polygons_distance_separated_squares_returns_gap() {
+        let result = polygons_distance(&square(0.0, 0.0, 1.0), &square(5.0, 0.5, 1.0));
+
+        assert_eq!(result, 3.0);
+    }
+
+    #[test]
+    fn polygons_distance_overlapping_squares_returns_zero() {
+        let result = polygons_distance(&square(0.0, 0.0, 1.0), &square(1.5, 0.0, 1.0));
+
+        assert_eq!(result, 0.0);
+    }
+}
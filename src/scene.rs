@@ -0,0 +1,423 @@
+//! Top-level game flow as a stack of scenes.
+//!
+//! The bottom of the stack is always the title screen. Starting a game
+//! pushes `Playing`, pausing pushes `Paused` on top of it and unpausing pops
+//! it again, so the world underneath is left exactly as it was. Only a
+//! `Playing` scene on top advances the simulation. A game that ends with a
+//! high score asks for a name on top of the game over screen first.
+
+use std::path::PathBuf;
+
+use crate::config::Settings;
+use crate::high_scores::{self, HighScores};
+use crate::input::Input;
+use crate::settings;
+use crate::world::World;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Scene {
+    Title,
+    Playing,
+    Paused,
+    GameOver,
+    /// Typing a name for the high score table.
+    EnterName,
+}
+
+/// Menu actions, independent of the keys they are bound to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Command {
+    /// Start, resume or restart.
+    Confirm,
+    /// Pause or resume.
+    Pause,
+    /// Leave the current scene, quitting from the title screen.
+    Back,
+    /// Delete the last character typed.
+    Erase,
+}
+
+pub struct Game {
+    width: f64,
+    height: f64,
+    stack: Vec<Scene>,
+    world: World,
+    input: Input,
+    quit: bool,
+    high_scores: HighScores,
+    /// Where the high scores are saved, `None` to keep them in memory.
+    high_scores_path: Option<PathBuf>,
+    name: String,
+    settings: Settings,
+}
+
+impl Game {
+    /// Game on the title screen, played by the given settings, with the
+    /// high scores loaded from the user's data directory.
+    pub fn new(settings: Settings) -> Self {
+        let path = HighScores::default_path();
+        let high_scores = path
+            .as_deref()
+            .map_or_else(HighScores::new, HighScores::load);
+        Game::with_settings(settings, high_scores, path)
+    }
+
+    /// Game with the given high score table, saved to `path` if there is
+    /// one.
+    pub fn with_high_scores(
+        width: f64,
+        height: f64,
+        high_scores: HighScores,
+        path: Option<PathBuf>,
+    ) -> Self {
+        let mut settings = Settings::default();
+        settings.window.width = width as u32;
+        settings.window.height = height as u32;
+        Game::with_settings(settings, high_scores, path)
+    }
+
+    /// Game played by the given settings, in an arena the size of the
+    /// window they ask for.
+    pub fn with_settings(
+        settings: Settings,
+        high_scores: HighScores,
+        path: Option<PathBuf>,
+    ) -> Self {
+        let width = settings.window.width as f64;
+        let height = settings.window.height as f64;
+        Game {
+            width,
+            height,
+            stack: vec![Scene::Title],
+            world: World::with_settings(width, height, settings.clone()),
+            input: Input::new(),
+            quit: false,
+            high_scores,
+            high_scores_path: path,
+            name: String::new(),
+            settings,
+        }
+    }
+
+    /// The scene on top of the stack.
+    pub fn scene(&self) -> Scene {
+        *self.stack.last().unwrap()
+    }
+
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    /// Plays on by new settings, in the game under way and the ones after
+    /// it. The window keeps its size until the game is started again.
+    pub fn apply_settings(&mut self, mut settings: Settings) {
+        settings.window = self.settings.window;
+        self.world.apply_settings(settings.clone());
+        self.settings = settings;
+    }
+
+    pub fn high_scores(&self) -> &HighScores {
+        &self.high_scores
+    }
+
+    /// Name typed so far for the high score table.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Whether the player asked to quit from the title screen.
+    pub fn should_quit(&self) -> bool {
+        self.quit
+    }
+
+    /// Held keys, kept while paused so nothing gets stuck down.
+    pub fn set_input(&mut self, input: Input) {
+        self.input = input;
+        self.world.set_input(input);
+    }
+
+    pub fn command(&mut self, command: Command) {
+        match (self.scene(), command) {
+            (Scene::Title, Command::Confirm) => {
+                self.restart();
+                self.stack.push(Scene::Playing);
+            }
+            (Scene::Title, Command::Back) => self.quit = true,
+            (Scene::Playing, Command::Pause | Command::Back) => self.stack.push(Scene::Paused),
+            (Scene::Paused, Command::Pause | Command::Confirm) => {
+                self.stack.pop();
+            }
+            (Scene::Paused | Scene::GameOver, Command::Back) => self.stack.truncate(1),
+            (Scene::GameOver, Command::Confirm) => {
+                self.restart();
+                *self.stack.last_mut().unwrap() = Scene::Playing;
+            }
+            (Scene::EnterName, Command::Confirm) => {
+                self.record_high_score();
+                self.stack.pop();
+            }
+            (Scene::EnterName, Command::Back) => {
+                self.stack.pop();
+            }
+            (Scene::EnterName, Command::Erase) => {
+                self.name.pop();
+            }
+            _ => {}
+        }
+    }
+
+    /// Typed text, only taken while entering a name.
+    pub fn type_character(&mut self, character: char) {
+        let typing = self.scene() == Scene::EnterName
+            && high_scores::is_name_character(character)
+            && self.name.chars().count() < settings::high_scores::NAME_LENGTH;
+        if typing {
+            self.name.push(character);
+        }
+    }
+
+    /// Runs the simulation if playing, and ends the game once the last
+    /// life is lost.
+    pub fn update(&mut self, dt: f64) {
+        if self.scene() != Scene::Playing {
+            return;
+        }
+        self.world.update(dt);
+        if self.world.is_game_over() {
+            *self.stack.last_mut().unwrap() = Scene::GameOver;
+            if self.high_scores.qualifies(self.points()) {
+                self.name.clear();
+                self.stack.push(Scene::EnterName);
+            }
+        }
+    }
+
+    fn points(&self) -> u64 {
+        self.world.score().map_or(0, |score| score.get_points())
+    }
+
+    fn record_high_score(&mut self) {
+        self.high_scores.insert(&self.name, self.points());
+        if let Some(path) = &self.high_scores_path {
+            if let Err(error) = self.high_scores.save(path) {
+                eprintln!(
+                    "Could not save high scores to {}: {}",
+                    path.display(),
+                    error
+                );
+            }
+        }
+    }
+
+    fn restart(&mut self) {
+        self.world = World::with_settings(self.width, self.height, self.settings.clone());
+        self.world.set_input(self.input);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::high_scores::HighScores;
+    use crate::scene::{Command, Game, Scene};
+    use crate::settings;
+
+    const WIDTH: f64 = 1024.0;
+    const HEIGHT: f64 = 512.0;
+
+    fn new_game(high_scores: HighScores) -> Game {
+        Game::with_high_scores(WIDTH, HEIGHT, high_scores, None)
+    }
+
+    fn playing() -> Game {
+        playing_with(unbeatable())
+    }
+
+    fn playing_with(high_scores: HighScores) -> Game {
+        let mut game = new_game(high_scores);
+        game.command(Command::Confirm);
+        game
+    }
+
+    fn unbeatable() -> HighScores {
+        let mut high_scores = HighScores::new();
+        for _ in 0..settings::high_scores::ENTRIES {
+            high_scores.insert("BEST", u64::MAX);
+        }
+        high_scores
+    }
+
+    fn play(game: &mut Game, seconds: f64) {
+        let steps = (seconds / settings::simulation::STEP).round() as u32;
+        for _ in 0..steps {
+            game.update(settings::simulation::STEP);
+        }
+    }
+
+    fn lose_all_lives(game: &mut Game) {
+        let player = game.world.player().unwrap();
+        // Long enough to respawn and for the invulnerability to wear off.
+        let respawn = settings::player::RESPAWN_TIME + settings::player::INVULNERABLE_TIME + 0.5;
+        for _ in 0..settings::player::LIVES {
+            game.world.healths.get_mut(player).unwrap().damage(u32::MAX);
+            play(game, respawn);
+        }
+    }
+
+    #[test]
+    fn new_starts_on_title() {
+        let game = new_game(unbeatable());
+
+        assert_eq!(game.scene(), Scene::Title);
+        assert!(!game.should_quit());
+    }
+
+    #[test]
+    fn command_confirm_on_title_starts_playing() {
+        let game = playing();
+
+        assert_eq!(game.scene(), Scene::Playing);
+    }
+
+    #[test]
+    fn command_back_on_title_quits() {
+        let mut game = new_game(unbeatable());
+
+        game.command(Command::Back);
+
+        assert!(game.should_quit());
+    }
+
+    #[test]
+    fn update_on_title_does_not_simulate() {
+        let mut game = new_game(unbeatable());
+
+        game.update(1.0);
+
+        assert_eq!(game.world().wave(), 0);
+    }
+
+    #[test]
+    fn update_while_paused_freezes_world() {
+        let mut game = playing();
+        play(&mut game, 0.5);
+        let player = game.world().player().unwrap();
+        let before = game.world().position(player);
+        let chasers = game.world().chasers.len();
+
+        game.command(Command::Pause);
+        game.update(20.0);
+
+        assert_eq!(game.scene(), Scene::Paused);
+        assert_eq!(game.world().position(player), before);
+        assert_eq!(game.world().chasers.len(), chasers);
+    }
+
+    #[test]
+    fn command_pause_twice_resumes_playing() {
+        let mut game = playing();
+
+        game.command(Command::Pause);
+        game.command(Command::Pause);
+
+        assert_eq!(game.scene(), Scene::Playing);
+    }
+
+    #[test]
+    fn command_back_while_paused_returns_to_title() {
+        let mut game = playing();
+
+        game.command(Command::Pause);
+        game.command(Command::Back);
+
+        assert_eq!(game.scene(), Scene::Title);
+        assert!(!game.should_quit());
+    }
+
+    #[test]
+    fn update_last_life_lost_ends_game() {
+        let mut game = playing();
+
+        lose_all_lives(&mut game);
+
+        assert_eq!(game.scene(), Scene::GameOver);
+    }
+
+    #[test]
+    fn command_confirm_on_game_over_restarts_with_fresh_world() {
+        let mut game = playing();
+        lose_all_lives(&mut game);
+
+        game.command(Command::Confirm);
+
+        assert_eq!(game.scene(), Scene::Playing);
+        assert!(!game.world().is_game_over());
+        assert_eq!(game.world().wave(), 0);
+    }
+
+    #[test]
+    fn update_game_over_with_high_score_asks_for_name() {
+        let mut game = playing_with(HighScores::new());
+
+        lose_all_lives(&mut game);
+
+        assert_eq!(game.scene(), Scene::EnterName);
+    }
+
+    #[test]
+    fn command_confirm_name_records_high_score() {
+        let mut game = playing_with(HighScores::new());
+        lose_all_lives(&mut game);
+        let points = game.world().score().unwrap().get_points();
+
+        for character in "Ann\tX".chars() {
+            game.type_character(character);
+        }
+        game.command(Command::Erase);
+        game.command(Command::Confirm);
+
+        assert_eq!(game.scene(), Scene::GameOver);
+        let entry = &game.high_scores().entries()[0];
+        assert_eq!(entry.name, "Ann");
+        assert_eq!(entry.points, points);
+    }
+
+    #[test]
+    fn command_back_while_entering_name_records_nothing() {
+        let mut game = playing_with(HighScores::new());
+        lose_all_lives(&mut game);
+
+        game.command(Command::Back);
+
+        assert_eq!(game.scene(), Scene::GameOver);
+        assert!(game.high_scores().entries().is_empty());
+    }
+
+    #[test]
+    fn type_character_outside_name_entry_is_ignored() {
+        let mut game = playing();
+
+        game.type_character('a');
+
+        assert_eq!(game.name(), "");
+    }
+
+    #[test]
+    fn apply_settings_changes_game_under_way_and_keeps_window() {
+        let mut game = playing();
+        let mut settings = game.settings().clone();
+        settings.window.width = 320;
+        settings.color.player = [0.0, 0.0, 1.0, 1.0];
+
+        game.apply_settings(settings);
+
+        let player = game.world().player().unwrap();
+        let sprite = game.world().sprites.get(player).unwrap();
+        assert_eq!(game.settings().window.width, WIDTH as u32);
+        assert_eq!(&sprite.color[..3], &[0.0, 0.0, 1.0]);
+        assert_eq!(game.world().settings().color.player, [0.0, 0.0, 1.0, 1.0]);
+    }
+}
@@ -0,0 +1,154 @@
+//! Points for staying alive and for dodging close.
+//!
+//! Points trickle in for every second survived. A near miss is worth a
+//! bonus, more the closer the chaser came, and near misses in quick
+//! succession build up a streak that multiplies everything scored. Getting
+//! hurt, or going too long without a near miss, loses the streak.
+
+use crate::config::ScoreSettings;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Score {
+    points: f64,
+    time_survived: f64,
+    streak: u32,
+    since_near_miss: f64,
+    settings: ScoreSettings,
+}
+
+impl Score {
+    pub fn new() -> Self {
+        Score::with_settings(ScoreSettings::default())
+    }
+
+    /// Score kept by the given rules.
+    pub fn with_settings(settings: ScoreSettings) -> Self {
+        Score {
+            points: 0.0,
+            time_survived: 0.0,
+            streak: 0,
+            since_near_miss: 0.0,
+            settings,
+        }
+    }
+
+    /// Keeps scoring by the given rules from now on, without touching what
+    /// was scored so far.
+    pub fn set_settings(&mut self, settings: ScoreSettings) {
+        self.settings = settings;
+    }
+
+    /// Whole points scored so far.
+    pub fn get_points(&self) -> u64 {
+        self.points as u64
+    }
+
+    pub fn get_time_survived(&self) -> f64 {
+        self.time_survived
+    }
+
+    /// Near misses since the streak was last lost.
+    pub fn get_streak(&self) -> u32 {
+        self.streak
+    }
+
+    /// Factor everything scored is multiplied by.
+    pub fn multiplier(&self) -> f64 {
+        (1.0 + self.streak as f64 * self.settings.streak_step).min(self.settings.max_multiplier)
+    }
+
+    /// Scores `dt` seconds of survival, losing the streak if it has gone
+    /// stale.
+    pub fn survive(&mut self, dt: f64) {
+        self.time_survived += dt;
+        self.points += self.settings.points_per_second * dt * self.multiplier();
+        self.since_near_miss += dt;
+        if self.since_near_miss >= self.settings.streak_timeout {
+            self.streak = 0;
+        }
+    }
+
+    pub fn hit(&mut self) {
+        self.streak = 0;
+    }
+
+    /// Scores a near miss that came within `closest` of the player and
+    /// extends the streak.
+    pub fn near_miss(&mut self, closest: f64) {
+        self.streak += 1;
+        self.since_near_miss = 0.0;
+        let closeness = (1.0 - closest / self.settings.near_miss_margin).clamp(0.0, 1.0);
+        self.points += self.settings.near_miss_points * (1.0 + closeness) * self.multiplier();
+    }
+}
+
+impl Default for Score {
+    fn default() -> Self {
+        Score::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::score::Score;
+    use crate::settings::score;
+
+    #[test]
+    fn survive_scores_points_per_second() {
+        let mut result = Score::new();
+
+        result.survive(2.0);
+
+        assert_eq!(result.get_points(), (2.0 * score::POINTS_PER_SECOND) as u64);
+        assert_eq!(result.get_time_survived(), 2.0);
+    }
+
+    #[test]
+    fn near_miss_closer_scores_more() {
+        let mut edge = Score::new();
+        let mut graze = Score::new();
+
+        edge.near_miss(score::NEAR_MISS_MARGIN);
+        graze.near_miss(0.0);
+
+        assert_eq!(edge.get_streak(), 1);
+        assert_eq!(
+            edge.get_points(),
+            (score::NEAR_MISS_POINTS * edge.multiplier()) as u64
+        );
+        assert_eq!(graze.get_points(), 2 * edge.get_points());
+    }
+
+    #[test]
+    fn near_miss_streak_raises_multiplier_up_to_max() {
+        let mut result = Score::new();
+
+        for _ in 0..100 {
+            result.near_miss(10.0);
+        }
+
+        assert_eq!(result.get_streak(), 100);
+        assert_eq!(result.multiplier(), score::MAX_MULTIPLIER);
+    }
+
+    #[test]
+    fn hit_loses_streak() {
+        let mut result = Score::new();
+        result.near_miss(10.0);
+
+        result.hit();
+
+        assert_eq!(result.get_streak(), 0);
+        assert_eq!(result.multiplier(), 1.0);
+    }
+
+    #[test]
+    fn survive_without_near_miss_for_timeout_loses_streak() {
+        let mut result = Score::new();
+        result.near_miss(10.0);
+
+        result.survive(score::STREAK_TIMEOUT);
+
+        assert_eq!(result.get_streak(), 0);
+    }
+}
@@ -4,18 +4,156 @@ pub mod color {
     pub const RED: [f32; 4] = [1.0, 0.0, 0.0, 1.0];
     pub const GREY: [f32; 4] = [0.25, 0.25, 0.25, 0.0];
     pub const DEBUG: [f32; 4] = [1.0, 0.0, 0.0, 0.5];
+    pub const DEBUG_TRIGGER: [f32; 4] = [0.0, 1.0, 0.0, 0.5];
+    pub const OBSTACLE: [f32; 4] = [0.5, 0.5, 0.5, 1.0];
+    pub const PAUSED: [f32; 4] = [0.0, 0.0, 0.0, 0.5];
+    pub const GAME_OVER: [f32; 4] = [0.5, 0.0, 0.0, 0.5];
+    pub const TEXT: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
 }
 
 pub mod window {
     pub const SIZE: (u32, u32) = (1024, 512);
 }
 
+pub mod config {
+    /// Directory under the user's config directory.
+    pub const DIRECTORY: &str = "dodge";
+    pub const FILE: &str = "settings.toml";
+    /// Seconds between looks at the settings file for changes.
+    pub const WATCH_INTERVAL: f64 = 0.5;
+}
+
+pub mod hud {
+    pub const FONT_SIZE: u32 = 18;
+    pub const TITLE_SIZE: u32 = 48;
+    /// Space between the text and the window edges.
+    pub const MARGIN: f64 = 16.0;
+    /// Baseline to baseline, as a multiple of the font size.
+    pub const LINE_SPACING: f64 = 1.4;
+    /// Seconds the frame rate is averaged over.
+    pub const FPS_INTERVAL: f64 = 0.5;
+}
+
 pub mod player {
     pub const SIZE: f64 = 20.0;
+    /// Top speed, reached when drag cancels out the acceleration.
     pub const SPEED: f64 = 250.0;
+    pub const ACCELERATION: f64 = 2500.0;
+    pub const DRAG: f64 = ACCELERATION / SPEED;
+    pub const MASS: f64 = 1.0;
+    pub const RESTITUTION: f64 = 0.5;
+    pub const HEALTH: u32 = 3;
+    pub const LIVES: u32 = 3;
+    /// Seconds damage is ignored for after a hit or a respawn.
+    pub const INVULNERABLE_TIME: f64 = 1.5;
+    /// Seconds between dying and coming back.
+    pub const RESPAWN_TIME: f64 = 2.0;
 }
 
 pub mod chaser {
     pub const SIZE: f64 = 30.0;
+    /// Top speed, reached when drag cancels out the acceleration.
     pub const SPEED: f64 = 225.0;
+    pub const ACCELERATION: f64 = 1125.0;
+    pub const DRAG: f64 = ACCELERATION / SPEED;
+    pub const MASS: f64 = 2.0;
+    pub const RESTITUTION: f64 = 0.5;
+    /// Health taken from a player on contact.
+    pub const DAMAGE: u32 = 1;
+}
+
+pub mod steering {
+    /// Chasers closer than this push away from each other.
+    pub const SEPARATION_RADIUS: f64 = 90.0;
+    pub const SEPARATION_WEIGHT: f64 = 1.5;
+    /// Longest look ahead, in seconds, when pursuing a moving target.
+    pub const MAX_PREDICTION: f64 = 1.0;
+    pub const SLOWING_RADIUS: f64 = 120.0;
+    /// How far ahead the wander circle is and how big it is.
+    pub const WANDER_DISTANCE: f64 = 60.0;
+    pub const WANDER_RADIUS: f64 = 40.0;
+    /// Most the wander angle can change, in radians per second.
+    pub const WANDER_JITTER: f64 = 6.0;
+}
+
+pub mod ai {
+    /// Seconds an idle chaser stands around before patrolling.
+    pub const IDLE_TIME: f64 = 1.0;
+    /// Seconds a chaser patrols before standing around again.
+    pub const PATROL_TIME: f64 = 3.0;
+    pub const STUN_TIME: f64 = 1.0;
+}
+
+pub mod waves {
+    /// Seconds from the start of one wave to the start of the next.
+    pub const INTERVAL: f64 = 10.0;
+    /// Seconds between the chasers of a single wave.
+    pub const STAGGER: f64 = 0.5;
+    /// Where along each edge chasers come in.
+    pub const OFFSETS: [f64; 2] = [0.25, 0.75];
+    pub const COUNT_START: f64 = 1.0;
+    pub const COUNT_STEP: f64 = 1.0;
+    pub const COUNT_LIMIT: f64 = 12.0;
+    /// Speed and size start out at the `chaser` values.
+    pub const SPEED_STEP: f64 = 10.0;
+    pub const SPEED_LIMIT: f64 = 350.0;
+    pub const SIZE_STEP: f64 = 2.0;
+    pub const SIZE_LIMIT: f64 = 50.0;
+}
+
+pub mod collision {
+    pub const CELL_SIZE: f64 = 64.0;
+    /// Most samples a sweep takes through the part of the motion where the
+    /// colliders can touch. Shapes thinner than that part over this many
+    /// could be stepped over.
+    pub const MAX_SWEEP_STEPS: u32 = 256;
+}
+
+pub mod arena {
+    /// Thick enough that nothing crosses a wall in a single update.
+    pub const WALL_THICKNESS: f64 = 256.0;
+    pub const RESOLVE_ITERATIONS: u32 = 4;
+    /// Boxes in the arena as center and half extents, the centers given as
+    /// fractions of the window size.
+    pub const OBSTACLES: [(f64, f64, f64, f64); 2] =
+        [(0.25, 0.5, 32.0, 64.0), (0.75, 0.5, 32.0, 64.0)];
+}
+
+pub mod navigation {
+    pub const CELL_SIZE: f64 = 32.0;
+    /// Room kept between path cells and obstacles.
+    pub const CLEARANCE: f64 = 24.0;
+    /// Seconds a path is followed before it is found again.
+    pub const REPLAN_INTERVAL: f64 = 0.5;
+}
+
+pub mod simulation {
+    /// Length of one simulation step in seconds.
+    pub const STEP: f64 = 1.0 / 120.0;
+    pub const MAX_STEPS_PER_FRAME: u32 = 8;
+}
+
+pub mod score {
+    pub const POINTS_PER_SECOND: f64 = 10.0;
+    /// A chaser passing this close to the player without touching is a
+    /// near miss.
+    pub const NEAR_MISS_MARGIN: f64 = 25.0;
+    /// Points for a near miss at the edge of the margin, twice that for
+    /// one that grazes the player.
+    pub const NEAR_MISS_POINTS: f64 = 50.0;
+    /// Multiplier gained for every near miss in a streak.
+    pub const STREAK_STEP: f64 = 0.5;
+    pub const MAX_MULTIPLIER: f64 = 5.0;
+    /// Seconds without a near miss before the streak is lost.
+    pub const STREAK_TIMEOUT: f64 = 5.0;
+}
+
+pub mod high_scores {
+    pub const ENTRIES: usize = 10;
+    pub const NAME_LENGTH: usize = 12;
+    /// Name recorded when none is entered.
+    pub const DEFAULT_NAME: &str = "PLAYER";
+    /// Directory under the user's data directory.
+    pub const DIRECTORY: &str = "dodge";
+    pub const FILE: &str = "high_scores.txt";
 }
@@ -0,0 +1,360 @@
+//! Waves of chasers entering from the arena edges.
+//!
+//! Everything about the waves lives in a `WaveConfig`: where chasers come
+//! in, how long apart waves and the chasers within a wave are, and how the
+//! count, speed and size of the chasers ramp up from one wave to the next.
+
+use crate::chaser::ChaserKind;
+use crate::config::{ChaserSettings, WaveSettings};
+use crate::vector2::Vector2;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Edge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+/// Place on an arena edge, `offset` runs from `0.0` at the left or top end
+/// to `1.0` at the right or bottom end.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpawnPoint {
+    pub edge: Edge,
+    pub offset: f64,
+}
+
+impl SpawnPoint {
+    pub fn new(edge: Edge, offset: f64) -> Self {
+        SpawnPoint { edge, offset }
+    }
+
+    /// Position of a chaser of radius `size` touching the edge from inside
+    /// a `width` by `height` arena.
+    pub fn position(&self, width: f64, height: f64, size: f64) -> Vector2 {
+        let along = |length: f64| (self.offset * length).max(size).min(length - size);
+        match self.edge {
+            Edge::Top => Vector2::new(along(width), size),
+            Edge::Bottom => Vector2::new(along(width), height - size),
+            Edge::Left => Vector2::new(size, along(height)),
+            Edge::Right => Vector2::new(width - size, along(height)),
+        }
+    }
+}
+
+/// Value growing by `step` each wave until it reaches `limit`. A negative
+/// step shrinks it down to the limit instead.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ramp {
+    pub start: f64,
+    pub step: f64,
+    pub limit: f64,
+}
+
+impl Ramp {
+    pub fn new(start: f64, step: f64, limit: f64) -> Self {
+        Ramp { start, step, limit }
+    }
+
+    /// Value in the wave with the given zero-based index.
+    pub fn at(&self, wave: u32) -> f64 {
+        let value = self.start + self.step * wave as f64;
+        if self.step < 0.0 {
+            value.max(self.limit)
+        } else {
+            value.min(self.limit)
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct WaveConfig {
+    /// Chasers take turns entering at these points, across waves.
+    pub spawn_points: Vec<SpawnPoint>,
+    /// Kinds of chaser to send, taking turns like the spawn points. Without
+    /// any, every chaser is a `ChaserKind::Brute`.
+    pub kinds: Vec<ChaserKind>,
+    /// Seconds from the start of one wave to the start of the next.
+    pub interval: f64,
+    /// Seconds between the chasers of a single wave.
+    pub stagger: f64,
+    pub count: Ramp,
+    pub speed: Ramp,
+    pub size: Ramp,
+}
+
+impl WaveConfig {
+    /// Waves entering at the given offsets along every edge, starting out
+    /// with chasers of the given size and speed.
+    pub fn from_settings(waves: &WaveSettings, chaser: &ChaserSettings) -> Self {
+        let spawn_points = [Edge::Top, Edge::Right, Edge::Bottom, Edge::Left]
+            .iter()
+            .flat_map(|&edge| {
+                waves
+                    .offsets
+                    .iter()
+                    .map(move |&offset| SpawnPoint::new(edge, offset))
+            })
+            .collect();
+        WaveConfig {
+            spawn_points,
+            kinds: waves.kinds.clone(),
+            interval: waves.interval,
+            stagger: waves.stagger,
+            count: Ramp::new(waves.count_start, waves.count_step, waves.count_limit),
+            speed: Ramp::new(chaser.speed, waves.speed_step, waves.speed_limit),
+            size: Ramp::new(chaser.size, waves.size_step, waves.size_limit),
+        }
+    }
+}
+
+impl Default for WaveConfig {
+    fn default() -> Self {
+        WaveConfig::from_settings(&WaveSettings::default(), &ChaserSettings::default())
+    }
+}
+
+/// A chaser the spawner wants created.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ChaserSpawn {
+    pub position: Vector2,
+    /// Top speed of the chaser.
+    pub speed: f64,
+    pub size: f64,
+    pub kind: ChaserKind,
+}
+
+/// Counts down to each wave and hands out its chasers one by one.
+pub struct Spawner {
+    config: WaveConfig,
+    width: f64,
+    height: f64,
+    /// Number of waves started so far.
+    wave: u32,
+    next_wave_in: f64,
+    next_point: usize,
+    next_kind: usize,
+    /// Chasers of started waves still waiting, with seconds left to wait.
+    pending: Vec<(f64, ChaserSpawn)>,
+}
+
+impl Spawner {
+    /// Spawner for a `width` by `height` arena, the first wave starts right
+    /// away.
+    pub fn new(config: WaveConfig, width: f64, height: f64) -> Self {
+        if config.interval <= 0.0 {
+            panic!("Interval between waves must be greater than 0");
+        }
+        Spawner {
+            config,
+            width,
+            height,
+            wave: 0,
+            next_wave_in: 0.0,
+            next_point: 0,
+            next_kind: 0,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Number of waves started so far, `1` during the first wave.
+    pub fn get_wave(&self) -> u32 {
+        self.wave
+    }
+
+    /// Sends the waves still to come by `config`, carrying on from the
+    /// current wave. Chasers already waiting come in as they were.
+    pub fn set_config(&mut self, config: WaveConfig) {
+        if config.interval <= 0.0 {
+            panic!("Interval between waves must be greater than 0");
+        }
+        self.next_wave_in = self.next_wave_in.min(config.interval);
+        self.next_point = self
+            .next_point
+            .checked_rem(config.spawn_points.len())
+            .unwrap_or(0);
+        self.next_kind = self.next_kind.checked_rem(config.kinds.len()).unwrap_or(0);
+        self.config = config;
+    }
+
+    /// Advances the timers and returns the chasers due this update.
+    pub fn update(&mut self, dt: f64) -> Vec<ChaserSpawn> {
+        for (wait, _) in self.pending.iter_mut() {
+            *wait -= dt;
+        }
+        self.next_wave_in -= dt;
+        while self.next_wave_in <= 0.0 {
+            // A wave that started part way through `dt` is already that far
+            // along by the end of it.
+            self.start_wave(-self.next_wave_in);
+            self.next_wave_in += self.config.interval;
+        }
+
+        let (due, waiting): (Vec<_>, Vec<_>) =
+            self.pending.drain(..).partition(|(wait, _)| *wait <= 0.0);
+        self.pending = waiting;
+        due.into_iter().map(|(_, spawn)| spawn).collect()
+    }
+
+    fn start_wave(&mut self, late: f64) {
+        let count = self.config.count.at(self.wave).max(0.0) as usize;
+        let speed = self.config.speed.at(self.wave);
+        let size = self.config.size.at(self.wave);
+        self.wave += 1;
+        if self.config.spawn_points.is_empty() {
+            return;
+        }
+        for i in 0..count {
+            let point = self.config.spawn_points[self.next_point];
+            self.next_point = (self.next_point + 1) % self.config.spawn_points.len();
+            let kind = match self.config.kinds.get(self.next_kind) {
+                Some(&kind) => {
+                    self.next_kind = (self.next_kind + 1) % self.config.kinds.len();
+                    kind
+                }
+                None => ChaserKind::Brute,
+            };
+            let spawn = ChaserSpawn {
+                position: point.position(self.width, self.height, size),
+                speed,
+                size,
+                kind,
+            };
+            let wait = self.config.stagger * i as f64 - late;
+            self.pending.push((wait, spawn));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chaser::ChaserKind;
+    use crate::spawner::{Edge, Ramp, SpawnPoint, Spawner, WaveConfig};
+    use crate::vector2::Vector2;
+
+    fn config() -> WaveConfig {
+        WaveConfig {
+            spawn_points: vec![
+                SpawnPoint::new(Edge::Top, 0.5),
+                SpawnPoint::new(Edge::Left, 0.5),
+            ],
+            kinds: vec![ChaserKind::Brute, ChaserKind::Hunter],
+            interval: 10.0,
+            stagger: 1.0,
+            count: Ramp::new(1.0, 1.0, 3.0),
+            speed: Ramp::new(100.0, 50.0, 1000.0),
+            size: Ramp::new(10.0, 5.0, 1000.0),
+        }
+    }
+
+    #[test]
+    fn ramp_at_grows_by_step_until_limit() {
+        let ramp = Ramp::new(1.0, 2.0, 6.0);
+
+        assert_eq!(ramp.at(0), 1.0);
+        assert_eq!(ramp.at(2), 5.0);
+        assert_eq!(ramp.at(3), 6.0);
+    }
+
+    #[test]
+    fn ramp_at_negative_step_shrinks_until_limit() {
+        let ramp = Ramp::new(10.0, -4.0, 3.0);
+
+        assert_eq!(ramp.at(1), 6.0);
+        assert_eq!(ramp.at(2), 3.0);
+    }
+
+    #[test]
+    fn spawn_point_position_touches_edge_from_inside() {
+        let top = SpawnPoint::new(Edge::Top, 0.25);
+        let right = SpawnPoint::new(Edge::Right, 1.0);
+
+        assert_eq!(top.position(200.0, 100.0, 10.0), Vector2::new(50.0, 10.0));
+        assert_eq!(
+            right.position(200.0, 100.0, 10.0),
+            Vector2::new(190.0, 90.0)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_interval_less_than_equal_zero_panic() {
+        let mut config = config();
+        config.interval = 0.0;
+        Spawner::new(config, 200.0, 100.0);
+    }
+
+    #[test]
+    fn update_first_call_spawns_first_wave() {
+        let mut spawner = Spawner::new(config(), 200.0, 100.0);
+
+        let result = spawner.update(0.1);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].position, Vector2::new(100.0, 10.0));
+        assert_eq!(result[0].speed, 100.0);
+        assert_eq!(spawner.get_wave(), 1);
+    }
+
+    #[test]
+    fn update_later_wave_has_more_faster_bigger_chasers() {
+        let mut spawner = Spawner::new(config(), 200.0, 100.0);
+
+        spawner.update(0.5);
+        let result: Vec<_> = (0..21).flat_map(|_| spawner.update(0.5)).collect();
+
+        assert_eq!(spawner.get_wave(), 2);
+        assert_eq!(result.len(), 2);
+        assert!(result.iter().all(|spawn| spawn.speed == 150.0));
+        assert!(result.iter().all(|spawn| spawn.size == 15.0));
+    }
+
+    #[test]
+    fn update_staggers_chasers_and_cycles_spawn_points() {
+        let mut config = config();
+        config.count = Ramp::new(3.0, 0.0, 3.0);
+        let mut spawner = Spawner::new(config, 200.0, 100.0);
+
+        let first = spawner.update(0.5);
+        let second = spawner.update(1.0);
+        let third = spawner.update(1.0);
+
+        assert_eq!(first.len(), 1);
+        assert_eq!(second.len(), 1);
+        assert_eq!(third.len(), 1);
+        assert_eq!(second[0].position, Vector2::new(10.0, 50.0));
+        assert_eq!(third[0].position, Vector2::new(100.0, 10.0));
+        assert_eq!(first[0].kind, ChaserKind::Brute);
+        assert_eq!(second[0].kind, ChaserKind::Hunter);
+        assert_eq!(third[0].kind, ChaserKind::Brute);
+    }
+
+    #[test]
+    fn update_without_spawn_points_still_counts_waves() {
+        let mut config = config();
+        config.spawn_points.clear();
+        let mut spawner = Spawner::new(config, 200.0, 100.0);
+
+        let result = spawner.update(25.0);
+
+        assert!(result.is_empty());
+        assert_eq!(spawner.get_wave(), 3);
+    }
+
+    #[test]
+    fn set_config_later_waves_follow_new_config() {
+        let mut spawner = Spawner::new(config(), 200.0, 100.0);
+        let mut faster = config();
+        faster.interval = 5.0;
+        faster.speed = Ramp::new(200.0, 50.0, 1000.0);
+        faster.kinds = vec![ChaserKind::Drifter];
+        spawner.update(0.5);
+
+        spawner.set_config(faster);
+        let result: Vec<_> = (0..10).flat_map(|_| spawner.update(0.5)).collect();
+
+        assert_eq!(spawner.get_wave(), 2);
+        assert_eq!(result[0].speed, 250.0);
+        assert_eq!(result[0].kind, ChaserKind::Drifter);
+    }
+}
@@ -0,0 +1,290 @@
+//! Steering behaviours for chasers.
+//!
+//! Every behaviour turns what a chaser knows about its surroundings into the
+//! velocity it would like to have. A chaser mixes several of them by weight,
+//! and the sum, capped at its top speed, is what it accelerates towards.
+
+use crate::settings;
+use crate::vector2::Vector2;
+
+/// Everything the behaviours get to look at, in world space.
+pub struct Situation<'a> {
+    pub position: Vector2,
+    pub velocity: Vector2,
+    pub max_speed: f64,
+    /// Position and velocity of whatever is being chased, if anything.
+    pub target: Option<(Vector2, Vector2)>,
+    /// Positions of the other chasers.
+    pub neighbours: &'a [Vector2],
+    pub dt: f64,
+}
+
+/// Heads straight for the target at full speed.
+pub fn seek(position: Vector2, target: Vector2, max_speed: f64) -> Vector2 {
+    (target - position).normalized() * max_speed
+}
+
+/// Heads straight away from the target at full speed.
+pub fn flee(position: Vector2, target: Vector2, max_speed: f64) -> Vector2 {
+    -seek(position, target, max_speed)
+}
+
+/// Seeks, but slows down linearly within `slowing_radius` of the target so
+/// it comes to rest on it instead of overshooting.
+pub fn arrive(position: Vector2, target: Vector2, max_speed: f64, slowing_radius: f64) -> Vector2 {
+    let distance = (target - position).magnitude();
+    let speed = max_speed * (distance / slowing_radius).min(1.0);
+    (target - position).normalized() * speed
+}
+
+/// Seeks where the target will be if it keeps its velocity, looking ahead
+/// as long as it takes to cover the distance, but at most `max_prediction`
+/// seconds.
+pub fn pursue(
+    position: Vector2,
+    target: Vector2,
+    target_velocity: Vector2,
+    max_speed: f64,
+    max_prediction: f64,
+) -> Vector2 {
+    let lookahead = ((target - position).magnitude() / max_speed).min(max_prediction);
+    seek(position, target + target_velocity * lookahead, max_speed)
+}
+
+/// Pushes away from every neighbour closer than `radius`, harder the closer
+/// it is.
+pub fn separation(
+    position: Vector2,
+    neighbours: &[Vector2],
+    radius: f64,
+    max_speed: f64,
+) -> Vector2 {
+    neighbours
+        .iter()
+        .map(|&neighbour| position - neighbour)
+        .filter(|offset| offset.magnitude() < radius)
+        .fold(Vector2::new(0.0, 0.0), |sum, offset| {
+            // A neighbour stacked right on top gives no direction to push
+            // in, normalizing leaves it at zero.
+            let strength = 1.0 - offset.magnitude() / radius;
+            sum + offset.normalized() * (strength * max_speed)
+        })
+        .truncated(max_speed)
+}
+
+/// Random but smooth meandering. A point on a circle ahead of the chaser
+/// jitters around the rim a little every step, and the chaser heads for it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Wander {
+    angle: f64,
+    seed: u64,
+}
+
+impl Wander {
+    /// The same seed always wanders the same way.
+    pub fn new(seed: u64) -> Self {
+        Wander {
+            angle: 0.0,
+            // Xorshift gets stuck on zero.
+            seed: seed.max(1),
+        }
+    }
+
+    pub fn steer(&mut self, velocity: Vector2, max_speed: f64, dt: f64) -> Vector2 {
+        let jitter = settings::steering::WANDER_JITTER * dt;
+        self.angle += (self.next_random() * 2.0 - 1.0) * jitter;
+        let heading = if velocity.magnitude() > 0.0 {
+            velocity.normalized()
+        } else {
+            Vector2::new(1.0, 0.0)
+        };
+        let rim = Vector2::new(1.0, 0.0).rotated(self.angle) * settings::steering::WANDER_RADIUS;
+        (heading * settings::steering::WANDER_DISTANCE + rim).normalized() * max_speed
+    }
+
+    /// Uniform number in `[0, 1)`.
+    fn next_random(&mut self) -> f64 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        (self.seed >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Behaviour {
+    Seek,
+    Flee,
+    Arrive { slowing_radius: f64 },
+    Pursue { max_prediction: f64 },
+    Wander(Wander),
+    Separation { radius: f64 },
+}
+
+impl Behaviour {
+    /// Desired velocity in the given situation. Behaviours that need a
+    /// target want nothing without one.
+    pub fn steer(&mut self, situation: &Situation) -> Vector2 {
+        let Situation {
+            position,
+            velocity,
+            max_speed,
+            target,
+            neighbours,
+            dt,
+        } = *situation;
+        let none = Vector2::new(0.0, 0.0);
+        match self {
+            Behaviour::Seek => target.map_or(none, |(target, _)| seek(position, target, max_speed)),
+            Behaviour::Flee => target.map_or(none, |(target, _)| flee(position, target, max_speed)),
+            Behaviour::Arrive { slowing_radius } => target.map_or(none, |(target, _)| {
+                arrive(position, target, max_speed, *slowing_radius)
+            }),
+            Behaviour::Pursue { max_prediction } => {
+                target.map_or(none, |(target, target_velocity)| {
+                    pursue(
+                        position,
+                        target,
+                        target_velocity,
+                        max_speed,
+                        *max_prediction,
+                    )
+                })
+            }
+            Behaviour::Wander(wander) => wander.steer(velocity, max_speed, dt),
+            Behaviour::Separation { radius } => {
+                separation(position, neighbours, *radius, max_speed)
+            }
+        }
+    }
+}
+
+/// Weighted sum of the behaviours' desired velocities, capped at top speed.
+pub fn blend(behaviours: &mut [(Behaviour, f64)], situation: &Situation) -> Vector2 {
+    behaviours
+        .iter_mut()
+        .fold(Vector2::new(0.0, 0.0), |sum, (behaviour, weight)| {
+            sum + behaviour.steer(situation) * *weight
+        })
+        .truncated(situation.max_speed)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::steering::{
+        arrive, blend, flee, pursue, seek, separation, Behaviour, Situation, Wander,
+    };
+    use crate::vector2::Vector2;
+
+    fn situation(neighbours: &[Vector2]) -> Situation<'_> {
+        Situation {
+            position: Vector2::new(0.0, 0.0),
+            velocity: Vector2::new(0.0, 0.0),
+            max_speed: 10.0,
+            target: Some((Vector2::new(100.0, 0.0), Vector2::new(0.0, 0.0))),
+            neighbours,
+            dt: 0.1,
+        }
+    }
+
+    #[test]
+    fn seek_returns_full_speed_towards_target() {
+        let result = seek(Vector2::new(0.0, 0.0), Vector2::new(0.0, 5.0), 10.0);
+
+        assert_eq!(result, Vector2::new(0.0, 10.0));
+    }
+
+    #[test]
+    fn flee_returns_full_speed_away_from_target() {
+        let result = flee(Vector2::new(0.0, 0.0), Vector2::new(0.0, 5.0), 10.0);
+
+        assert_eq!(result, Vector2::new(0.0, -10.0));
+    }
+
+    #[test]
+    fn arrive_inside_slowing_radius_slows_down() {
+        let far = arrive(Vector2::new(0.0, 0.0), Vector2::new(50.0, 0.0), 10.0, 20.0);
+        let near = arrive(Vector2::new(0.0, 0.0), Vector2::new(5.0, 0.0), 10.0, 20.0);
+
+        assert_eq!(far, Vector2::new(10.0, 0.0));
+        assert_eq!(near, Vector2::new(2.5, 0.0));
+    }
+
+    #[test]
+    fn pursue_moving_target_leads_it() {
+        let result = pursue(
+            Vector2::new(0.0, 0.0),
+            Vector2::new(10.0, 0.0),
+            Vector2::new(0.0, 10.0),
+            10.0,
+            5.0,
+        );
+
+        // One second to get there, by which time the target is at (10, 10).
+        assert!((result - Vector2::new(1.0, 1.0).normalized() * 10.0).magnitude() < 1e-12);
+    }
+
+    #[test]
+    fn pursue_prediction_capped_at_max_prediction() {
+        let result = pursue(
+            Vector2::new(0.0, 0.0),
+            Vector2::new(100.0, 0.0),
+            Vector2::new(0.0, 100.0),
+            10.0,
+            1.0,
+        );
+
+        assert!((result - Vector2::new(1.0, 1.0).normalized() * 10.0).magnitude() < 1e-12);
+    }
+
+    #[test]
+    fn separation_pushes_away_from_close_neighbours_only() {
+        let neighbours = [Vector2::new(5.0, 0.0), Vector2::new(0.0, 50.0)];
+
+        let result = separation(Vector2::new(0.0, 0.0), &neighbours, 10.0, 10.0);
+
+        assert_eq!(result, Vector2::new(-5.0, 0.0));
+    }
+
+    #[test]
+    fn wander_same_seed_wanders_the_same_way() {
+        let mut first = Wander::new(7);
+        let mut second = Wander::new(7);
+        let velocity = Vector2::new(1.0, 0.0);
+
+        for _ in 0..10 {
+            let a = first.steer(velocity, 10.0, 0.1);
+            let b = second.steer(velocity, 10.0, 0.1);
+            assert_eq!(a, b);
+            assert!((a.magnitude() - 10.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn steer_without_target_wants_nothing() {
+        let mut situation = situation(&[]);
+        situation.target = None;
+
+        let result = Behaviour::Seek.steer(&situation);
+
+        assert_eq!(result, Vector2::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn blend_weights_behaviours_and_caps_at_max_speed() {
+        let neighbours = [Vector2::new(0.0, 5.0)];
+        let situation = situation(&neighbours);
+        let mut half_seek = [(Behaviour::Seek, 0.5)];
+        let mut everything = [
+            (Behaviour::Seek, 1.0),
+            (Behaviour::Separation { radius: 10.0 }, 1.0),
+        ];
+
+        let half = blend(&mut half_seek, &situation);
+        let capped = blend(&mut everything, &situation);
+
+        assert_eq!(half, Vector2::new(5.0, 0.0));
+        assert!((capped.magnitude() - 10.0).abs() < 1e-12);
+        assert!(capped.y < 0.0);
+    }
+}
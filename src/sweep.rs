@@ -0,0 +1,164 @@
+//! Time of impact between moving colliders.
+//!
+//! Times are fractions of a frame's motion, `0.0` at the start positions and
+//! `1.0` once both colliders have moved by their full motion.
+
+use crate::vector2::Vector2;
+
+/// First time in `[0, 1]` two circles touch. `offset` is the position of the
+/// first circle relative to the second, `motion` its motion relative to the
+/// second's.
+pub(crate) fn circles_time_of_impact(
+    offset: Vector2,
+    motion: Vector2,
+    radius_sum: f64,
+) -> Option<f64> {
+    // Solve |offset + motion * t| = radius_sum for the smaller root.
+    let c = offset.dot(offset) - radius_sum * radius_sum;
+    if c < 0.0 {
+        return Some(0.0);
+    }
+    let a = motion.dot(motion);
+    let b = 2.0 * offset.dot(motion);
+    if a == 0.0 || b >= 0.0 {
+        return None;
+    }
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let t = (-b - discriminant.sqrt()) / (2.0 * a);
+    if t <= 1.0 {
+        Some(t.max(0.0))
+    } else {
+        None
+    }
+}
+
+/// Part of `[0, 1]` during which two projections on an axis overlap, the
+/// first moving `speed` along the axis over the whole motion. `None` if they
+/// never do.
+pub(crate) fn projection_window(
+    (a_min, a_max): (f64, f64),
+    (b_min, b_max): (f64, f64),
+    speed: f64,
+) -> Option<(f64, f64)> {
+    if speed == 0.0 {
+        return (a_min < b_max && b_min < a_max).then_some((0.0, 1.0));
+    }
+    let touch = (b_min - a_max) / speed;
+    let leave = (b_max - a_min) / speed;
+    let enter = touch.min(leave).max(0.0);
+    let exit = touch.max(leave).min(1.0);
+    (enter <= exit).then_some((enter, exit))
+}
+
+/// First time in `[0, 1]` `overlaps_at` holds, found by stepping through the
+/// motion in `steps` samples and bisecting the first overlapping one. Shapes
+/// that move less than their own size per step cannot be skipped over.
+pub(crate) fn sampled_time_of_impact<F>(steps: u32, mut overlaps_at: F) -> Option<f64>
+where
+    F: FnMut(f64) -> bool,
+{
+    if overlaps_at(0.0) {
+        return Some(0.0);
+    }
+    let steps = steps.max(1);
+    let mut previous = 0.0;
+    for step in 1..=steps {
+        let t = step as f64 / steps as f64;
+        if overlaps_at(t) {
+            let (mut free, mut hit) = (previous, t);
+            for _ in 0..BISECTION_ITERATIONS {
+                let middle = (free + hit) / 2.0;
+                if overlaps_at(middle) {
+                    hit = middle;
+                } else {
+                    free = middle;
+                }
+            }
+            return Some(hit);
+        }
+        previous = t;
+    }
+    None
+}
+
+const BISECTION_ITERATIONS: u32 = 24;
+
+#[cfg(test)]
+mod tests {
+    use crate::sweep::{circles_time_of_impact, projection_window, sampled_time_of_impact};
+    use crate::vector2::Vector2;
+
+    #[test]
+    fn circles_time_of_impact_head_on_returns_contact_time() {
+        let result = circles_time_of_impact(Vector2::new(-10.0, 0.0), Vector2::new(20.0, 0.0), 2.0);
+
+        assert_eq!(result, Some(0.4));
+    }
+
+    #[test]
+    fn circles_time_of_impact_moving_apart_returns_none() {
+        let result =
+            circles_time_of_impact(Vector2::new(-10.0, 0.0), Vector2::new(-20.0, 0.0), 2.0);
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn circles_time_of_impact_passing_by_returns_none() {
+        let result = circles_time_of_impact(Vector2::new(-10.0, 3.0), Vector2::new(20.0, 0.0), 2.0);
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn circles_time_of_impact_too_short_motion_returns_none() {
+        let result = circles_time_of_impact(Vector2::new(-10.0, 0.0), Vector2::new(5.0, 0.0), 2.0);
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn circles_time_of_impact_already_overlapping_returns_zero() {
+        let result = circles_time_of_impact(Vector2::new(-1.0, 0.0), Vector2::new(5.0, 0.0), 2.0);
+
+        assert_eq!(result, Some(0.0));
+    }
+
+    #[test]
+    fn projection_window_crossing_returns_overlapping_stretch() {
+        let result = projection_window((-10.0, -8.0), (0.0, 2.0), 20.0);
+
+        assert_eq!(result, Some((0.4, 0.6)));
+    }
+
+    #[test]
+    fn projection_window_falling_short_returns_none() {
+        let result = projection_window((-10.0, -8.0), (0.0, 2.0), 5.0);
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn projection_window_still_and_apart_returns_none() {
+        let result = projection_window((-10.0, -8.0), (0.0, 2.0), 0.0);
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn sampled_time_of_impact_finds_first_overlap() {
+        let result = sampled_time_of_impact(10, |t| (0.33..0.5).contains(&t));
+
+        assert!((result.unwrap() - 0.33).abs() < 1e-6);
+    }
+
+    #[test]
+    fn sampled_time_of_impact_never_overlapping_returns_none() {
+        let result = sampled_time_of_impact(10, |_| false);
+
+        assert!(result.is_none());
+    }
+}
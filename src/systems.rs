@@ -0,0 +1,510 @@
+//! Systems run by the world every step, each over the components it needs.
+
+use crate::ai::{AiState, Perception};
+use crate::arena::Arena;
+use crate::broad_phase::SpatialHash;
+use crate::chaser::Chaser;
+use crate::collider::Collider;
+use crate::collides::Collides;
+use crate::collision_events::{CollisionEvent, ContactTracker};
+use crate::components::{ContactDamage, Health, Sprite};
+use crate::ecs::{Components, Entity};
+use crate::navigation::NavGrid;
+use crate::physics::{self, RigidBody};
+use crate::player::Player;
+use crate::proximity::{NearMiss, ProximityTracker};
+use crate::ray;
+use crate::score::Score;
+use crate::steering::{self, Situation};
+use crate::vector2::Vector2;
+
+/// Accelerates players in the direction of their held keys.
+pub fn player_control(
+    players: &Components<Player>,
+    bodies: &mut Components<RigidBody>,
+    acceleration: f64,
+) {
+    for (entity, player) in players.iter() {
+        if let Some(body) = bodies.get_mut(entity) {
+            body.acceleration = player.direction() * acceleration;
+        }
+    }
+}
+
+/// Lets each chaser's brain look at the nearest player and decide what to
+/// do next.
+pub fn think(
+    chasers: &mut Components<Chaser>,
+    players: &Components<Player>,
+    colliders: &Components<Collider>,
+    bodies: &Components<RigidBody>,
+    arena: &Arena,
+    dt: f64,
+) {
+    let walls: Vec<&Collider> = arena.walls().iter().collect();
+    let targets = player_targets(players, colliders, bodies);
+    for (entity, chaser) in chasers.iter_mut() {
+        let Some(position) = colliders
+            .get(entity)
+            .map(|collider| collider.get_position())
+        else {
+            continue;
+        };
+        let target = nearest(&targets, position);
+        let perception = Perception {
+            distance: target.map(|(target, _)| (target - position).magnitude()),
+            line_of_sight: target
+                .is_some_and(|(target, _)| ray::line_of_sight(position, target, &walls)),
+        };
+        chaser.brain.think(perception, dt);
+    }
+}
+
+/// Stuns chasers that bump into a player.
+pub fn stun_on_contact(
+    events: &[CollisionEvent<Entity>],
+    chasers: &mut Components<Chaser>,
+    players: &Components<Player>,
+) {
+    for event in events {
+        let CollisionEvent::Enter(a, b) = *event else {
+            continue;
+        };
+        for (chaser, other) in [(a, b), (b, a)] {
+            if !players.contains(other) {
+                continue;
+            }
+            if let Some(chaser) = chasers.get_mut(chaser) {
+                chaser.brain.stun();
+            }
+        }
+    }
+}
+
+/// Blends the steering behaviours for each chaser's state against the
+/// nearest player and the other chasers, and accelerates it towards the
+/// velocity they want. A chased player the chaser can't move straight to
+/// is swapped for the next waypoint of a path around the walls in the way.
+/// Stunned chasers just drift.
+pub fn steer(
+    chasers: &mut Components<Chaser>,
+    players: &Components<Player>,
+    colliders: &Components<Collider>,
+    bodies: &mut Components<RigidBody>,
+    arena: &Arena,
+    navigation: &NavGrid,
+    dt: f64,
+) {
+    let walls: Vec<&Collider> = arena.walls().iter().collect();
+    let targets = player_targets(players, colliders, bodies);
+    let positions: Vec<(Entity, Vector2)> = chasers
+        .iter()
+        .filter_map(|(entity, _)| Some((entity, colliders.get(entity)?.get_position())))
+        .collect();
+
+    for (entity, chaser) in chasers.iter_mut() {
+        let (Some(collider), Some(body)) = (colliders.get(entity), bodies.get_mut(entity)) else {
+            continue;
+        };
+        let position = collider.get_position();
+        let state = chaser.brain.get_state();
+        let mut target = nearest(&targets, position);
+        if state == AiState::Chase {
+            let route = &mut chaser.route;
+            target = target.map(|(target, velocity)| {
+                if ray::clear_path(position, target, collider.min_extent(), &walls) {
+                    route.clear();
+                    return (target, velocity);
+                }
+                match navigation.follow(route, position, target, dt) {
+                    Some(waypoint) => (waypoint, Vector2::new(0.0, 0.0)),
+                    None => (target, velocity),
+                }
+            });
+        } else {
+            chaser.route.clear();
+        }
+        let neighbours: Vec<Vector2> = positions
+            .iter()
+            .filter(|(other, _)| *other != entity)
+            .map(|&(_, position)| position)
+            .collect();
+        let situation = Situation {
+            position,
+            velocity: body.velocity,
+            max_speed: chaser.speed,
+            target,
+            neighbours: &neighbours,
+            dt,
+        };
+        let behaviours = match state {
+            AiState::Idle => &mut chaser.idle,
+            AiState::Patrol => &mut chaser.patrol,
+            AiState::Chase => &mut chaser.chase,
+            AiState::Flee => &mut chaser.flee,
+            AiState::Stunned => {
+                body.acceleration = Vector2::new(0.0, 0.0);
+                continue;
+            }
+        };
+        // Drag cancels this out exactly at the desired velocity.
+        body.acceleration = steering::blend(behaviours, &situation) * body.drag;
+    }
+}
+
+/// Position and velocity of every living player.
+fn player_targets(
+    players: &Components<Player>,
+    colliders: &Components<Collider>,
+    bodies: &Components<RigidBody>,
+) -> Vec<(Vector2, Vector2)> {
+    players
+        .iter()
+        .filter(|(_, player)| player.is_alive())
+        .filter_map(|(entity, _)| {
+            let position = colliders.get(entity)?.get_position();
+            let velocity = bodies
+                .get(entity)
+                .map_or(Vector2::new(0.0, 0.0), |body| body.velocity);
+            Some((position, velocity))
+        })
+        .collect()
+}
+
+fn nearest(targets: &[(Vector2, Vector2)], position: Vector2) -> Option<(Vector2, Vector2)> {
+    targets.iter().copied().min_by(|(a, _), (b, _)| {
+        let a_distance = (*a - position).magnitude();
+        let b_distance = (*b - position).magnitude();
+        a_distance.partial_cmp(&b_distance).unwrap()
+    })
+}
+
+/// Moves every body by its velocity, remembering where it was before.
+pub fn integrate(
+    bodies: &mut Components<RigidBody>,
+    colliders: &mut Components<Collider>,
+    previous_positions: &mut Components<Vector2>,
+    dt: f64,
+) {
+    for (entity, body) in bodies.iter_mut() {
+        if let Some(collider) = colliders.get_mut(entity) {
+            let position = collider.get_position();
+            previous_positions.insert(entity, position);
+            collider.set_position(position + body.integrate(dt));
+        }
+    }
+}
+
+/// Pushes bodies back out of the arena walls and bounces them off.
+pub fn confine(
+    arena: &Arena,
+    colliders: &mut Components<Collider>,
+    bodies: &mut Components<RigidBody>,
+) {
+    for (entity, body) in bodies.iter_mut() {
+        if let Some(collider) = colliders.get_mut(entity) {
+            for manifold in arena.resolve(collider) {
+                body.bounce(manifold.normal);
+            }
+        }
+    }
+}
+
+/// Finds every overlapping pair, including ones that passed through each
+/// other since their previous positions, and turns them into events.
+pub fn detect_collisions(
+    colliders: &Components<Collider>,
+    previous_positions: &Components<Vector2>,
+    broad_phase: &mut SpatialHash,
+    contacts: &mut ContactTracker<Entity>,
+) -> Vec<CollisionEvent<Entity>> {
+    let entries: Vec<(Entity, &Collider, Vector2)> = colliders
+        .iter()
+        .map(|(entity, collider)| {
+            let motion = previous_positions
+                .get(entity)
+                .map_or(Vector2::new(0.0, 0.0), |&previous| {
+                    collider.get_position() - previous
+                });
+            (entity, collider, motion)
+        })
+        .collect();
+
+    broad_phase.clear();
+    for (id, (_, collider, motion)) in entries.iter().enumerate() {
+        broad_phase.insert_swept(id, collider, *motion);
+    }
+    let pairs: Vec<(Entity, Entity)> = broad_phase
+        .candidate_pairs()
+        .into_iter()
+        .filter(|&(a, b)| {
+            let (_, collider, motion) = entries[a];
+            let (_, other, other_motion) = entries[b];
+            collider.collides_with(other) || {
+                let mut start = collider.clone();
+                start.set_position(collider.get_position() - motion);
+                let mut other_start = other.clone();
+                other_start.set_position(other.get_position() - other_motion);
+                start
+                    .time_of_impact(motion, &other_start, other_motion)
+                    .is_some()
+            }
+        })
+        .map(|(a, b)| (entries[a].0, entries[b].0))
+        .collect();
+    contacts.update(&pairs)
+}
+
+/// Separates solid colliders that are touching and exchanges momentum
+/// between their bodies. Colliders without a body don't move.
+pub fn resolve_contacts(
+    events: &[CollisionEvent<Entity>],
+    arena: &Arena,
+    colliders: &mut Components<Collider>,
+    bodies: &mut Components<RigidBody>,
+) {
+    for event in events {
+        let (a, b) = match *event {
+            CollisionEvent::Enter(a, b) | CollisionEvent::Stay(a, b) => (a, b),
+            CollisionEvent::Exit(..) => continue,
+        };
+        let Some((collider, other)) = colliders.get_pair_mut(a, b) else {
+            continue;
+        };
+        match (bodies.contains(a), bodies.contains(b)) {
+            (true, true) => {
+                if let Some(manifold) = collider.separate_from(other) {
+                    let (body, other_body) = bodies.get_pair_mut(a, b).unwrap();
+                    physics::resolve_collision(body, other_body, &manifold);
+                }
+            }
+            (true, false) => push_out(collider, other, bodies.get_mut(a).unwrap()),
+            (false, true) => push_out(other, collider, bodies.get_mut(b).unwrap()),
+            (false, false) => continue,
+        }
+        // Separating can push either one back into a wall.
+        arena.resolve(collider);
+        arena.resolve(other);
+    }
+}
+
+/// Moves a collider fully out of a static one and bounces its body off.
+fn push_out(collider: &mut Collider, obstacle: &Collider, body: &mut RigidBody) {
+    if collider.is_trigger() || obstacle.is_trigger() {
+        return;
+    }
+    if let Some(manifold) = collider.contact(obstacle) {
+        let position = collider.get_position() + manifold.minimum_translation();
+        collider.set_position(position);
+        body.bounce(manifold.normal);
+    }
+}
+
+/// Hurts entities with health for as long as something with contact
+/// damage touches them, as often as their invulnerability allows. Returns
+/// the entities that lost health.
+pub fn contact_damage(
+    events: &[CollisionEvent<Entity>],
+    damages: &Components<ContactDamage>,
+    healths: &mut Components<Health>,
+) -> Vec<Entity> {
+    let mut hurt = Vec::new();
+    for event in events {
+        let (a, b) = match *event {
+            CollisionEvent::Enter(a, b) | CollisionEvent::Stay(a, b) => (a, b),
+            CollisionEvent::Exit(..) => continue,
+        };
+        for (victim, source) in [(a, b), (b, a)] {
+            if let (Some(damage), Some(health)) = (damages.get(source), healths.get_mut(victim)) {
+                if health.damage(damage.amount) {
+                    hurt.push(victim);
+                }
+            }
+        }
+    }
+    hurt
+}
+
+/// Counts down invulnerability and dims entities while it lasts.
+pub fn invulnerability(
+    healths: &mut Components<Health>,
+    sprites: &mut Components<Sprite>,
+    dt: f64,
+) {
+    for (entity, health) in healths.iter_mut() {
+        health.update(dt);
+        if let Some(sprite) = sprites.get_mut(entity) {
+            sprite.color[3] = if health.is_invulnerable() { 0.25 } else { 1.0 };
+        }
+    }
+}
+
+/// Kills players whose health ran out, taking them out of the collision
+/// world, and brings them back at `respawn_point` `respawn_time` seconds
+/// later.
+pub fn player_lives(
+    players: &mut Components<Player>,
+    healths: &mut Components<Health>,
+    colliders: &mut Components<Collider>,
+    bodies: &mut Components<RigidBody>,
+    respawn_point: Vector2,
+    respawn_time: f64,
+    dt: f64,
+) {
+    for (entity, player) in players.iter_mut() {
+        let (Some(health), Some(collider)) = (healths.get_mut(entity), colliders.get_mut(entity))
+        else {
+            continue;
+        };
+        let respawned = if player.is_alive() {
+            if health.is_depleted() {
+                player.die(respawn_time);
+                collider.disable();
+            }
+            false
+        } else {
+            player.update(dt)
+        };
+        if respawned {
+            health.restore();
+            collider.set_position(respawn_point);
+            collider.enable();
+        }
+        if respawned || !player.is_alive() {
+            if let Some(body) = bodies.get_mut(entity) {
+                body.velocity = Vector2::new(0.0, 0.0);
+            }
+        }
+    }
+}
+
+/// Near misses between living players and chasers, passing within
+/// `margin` of each other without touching. Chasers near a player when it
+/// dies are forgotten rather than counted.
+pub fn near_misses(
+    players: &Components<Player>,
+    chasers: &Components<Chaser>,
+    colliders: &Components<Collider>,
+    proximity: &mut ProximityTracker<Entity>,
+    margin: f64,
+) -> Vec<NearMiss<Entity>> {
+    let mut pairs = Vec::new();
+    for (player_entity, player) in players.iter() {
+        let Some(player_collider) = colliders.get(player_entity) else {
+            continue;
+        };
+        if !player.is_alive() || !player_collider.is_enabled() {
+            proximity.remove(player_entity);
+            continue;
+        }
+        for (chaser, _) in chasers.iter() {
+            if let Some(collider) = colliders.get(chaser) {
+                let proximity = player_collider.proximity(collider, margin);
+                pairs.push((player_entity, chaser, proximity));
+            }
+        }
+    }
+    proximity.update(&pairs)
+}
+
+/// Scores survival and near misses for living players, and loses the
+/// streak of every player in `hurt`.
+pub fn scoring(
+    scores: &mut Components<Score>,
+    players: &Components<Player>,
+    near_misses: &[NearMiss<Entity>],
+    hurt: &[Entity],
+    dt: f64,
+) {
+    for (entity, score) in scores.iter_mut() {
+        if hurt.contains(&entity) {
+            score.hit();
+        }
+        if !players.get(entity).is_some_and(|player| player.is_alive()) {
+            continue;
+        }
+        score.survive(dt);
+        for near_miss in near_misses {
+            if near_miss.a == entity || near_miss.b == entity {
+                score.near_miss(near_miss.closest);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collision_events::CollisionEvent;
+    use crate::components::{ContactDamage, Health, Sprite};
+    use crate::ecs::{Components, Entities};
+    use crate::settings::color;
+    use crate::systems::{contact_damage, invulnerability};
+
+    #[test]
+    fn contact_damage_while_touching_damages_again_after_invulnerability() {
+        let mut entities = Entities::new();
+        let victim = entities.create();
+        let source = entities.create();
+        let mut damages = Components::new();
+        let mut healths = Components::new();
+        let mut sprites = Components::new();
+        damages.insert(source, ContactDamage { amount: 1 });
+        healths.insert(victim, Health::with_invulnerability(3, 1.0));
+        sprites.insert(victim, Sprite::new(color::WHITE, 1.0));
+
+        contact_damage(
+            &[CollisionEvent::Enter(victim, source)],
+            &damages,
+            &mut healths,
+        );
+        invulnerability(&mut healths, &mut sprites, 0.5);
+        let dimmed = sprites.get(victim).unwrap().color[3];
+        contact_damage(
+            &[CollisionEvent::Stay(victim, source)],
+            &damages,
+            &mut healths,
+        );
+        let after_stay = healths.get(victim).unwrap().get_current();
+        invulnerability(&mut healths, &mut sprites, 0.5);
+        contact_damage(
+            &[CollisionEvent::Stay(victim, source)],
+            &damages,
+            &mut healths,
+        );
+
+        assert!(dimmed < 1.0);
+        assert_eq!(after_stay, 2);
+        assert_eq!(healths.get(victim).unwrap().get_current(), 1);
+    }
+
+    #[test]
+    fn invulnerability_worn_off_restores_color() {
+        let mut entities = Entities::new();
+        let entity = entities.create();
+        let mut healths = Components::new();
+        let mut sprites = Components::new();
+        let mut health = Health::with_invulnerability(3, 1.0);
+        health.damage(1);
+        healths.insert(entity, health);
+        sprites.insert(entity, Sprite::new(color::WHITE, 1.0));
+
+        invulnerability(&mut healths, &mut sprites, 1.0);
+
+        assert_eq!(sprites.get(entity).unwrap().color, color::WHITE);
+    }
+
+    #[test]
+    fn contact_damage_between_entities_without_damage_does_nothing() {
+        let mut entities = Entities::new();
+        let first = entities.create();
+        let second = entities.create();
+        let damages = Components::new();
+        let mut healths = Components::new();
+        healths.insert(first, Health::new(3));
+
+        let events = [CollisionEvent::Enter(first, second)];
+        contact_damage(&events, &damages, &mut healths);
+
+        assert_eq!(healths.get(first).unwrap().get_current(), 3);
+    }
+}
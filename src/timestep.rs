@@ -0,0 +1,104 @@
+/// Turns variable frame times into a whole number of fixed simulation steps.
+///
+/// Leftover time is carried over to the next frame, and how far it reaches
+/// into the next step is the blend factor for rendering between the previous
+/// and the current simulation state.
+pub struct FixedTimestep {
+    step: f64,
+    max_steps: u32,
+    accumulator: f64,
+}
+
+impl FixedTimestep {
+    pub fn new(step: f64, max_steps: u32) -> Self {
+        if step <= 0.0 {
+            panic!("Step of fixed timestep must be greater than 0");
+        }
+        FixedTimestep {
+            step,
+            max_steps,
+            accumulator: 0.0,
+        }
+    }
+
+    pub fn get_step(&self) -> f64 {
+        self.step
+    }
+
+    /// Adds a frame's time and returns how many steps to simulate. After a
+    /// long hitch at most `max_steps` are run and the rest of the time is
+    /// dropped, so a slow machine falls behind instead of locking up.
+    pub fn advance(&mut self, dt: f64) -> u32 {
+        self.accumulator += dt;
+        let steps = (self.accumulator / self.step).floor() as u32;
+        if steps > self.max_steps {
+            self.accumulator = 0.0;
+            return self.max_steps;
+        }
+        self.accumulator -= steps as f64 * self.step;
+        steps
+    }
+
+    /// Share of a step left over, `0.0` is exactly the current state.
+    pub fn alpha(&self) -> f64 {
+        self.accumulator / self.step
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::timestep::FixedTimestep;
+
+    #[test]
+    #[should_panic]
+    fn new_step_less_than_equal_zero_panic() {
+        FixedTimestep::new(0.0, 1);
+    }
+
+    #[test]
+    fn advance_less_than_step_returns_no_steps() {
+        let mut timestep = FixedTimestep::new(0.25, 8);
+
+        let result = timestep.advance(0.125);
+
+        assert_eq!(result, 0);
+        assert_eq!(timestep.alpha(), 0.5);
+    }
+
+    #[test]
+    fn advance_carries_leftover_time_over() {
+        let mut timestep = FixedTimestep::new(0.25, 8);
+
+        let first = timestep.advance(0.375);
+        let second = timestep.advance(0.125);
+
+        assert_eq!(first, 1);
+        assert_eq!(second, 1);
+        assert_eq!(timestep.alpha(), 0.0);
+    }
+
+    #[test]
+    fn advance_same_total_time_same_step_count() {
+        let mut even = FixedTimestep::new(0.25, 8);
+        let mut uneven = FixedTimestep::new(0.25, 8);
+
+        let even_steps: u32 = (0..8).map(|_| even.advance(0.125)).sum();
+        let uneven_steps: u32 = [0.5, 0.0625, 0.1875, 0.25]
+            .iter()
+            .map(|&dt| uneven.advance(dt))
+            .sum();
+
+        assert_eq!(even_steps, 4);
+        assert_eq!(uneven_steps, 4);
+    }
+
+    #[test]
+    fn advance_long_hitch_capped_at_max_steps() {
+        let mut timestep = FixedTimestep::new(0.25, 8);
+
+        let result = timestep.advance(10.0);
+
+        assert_eq!(result, 8);
+        assert_eq!(timestep.alpha(), 0.0);
+    }
+}
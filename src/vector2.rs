@@ -1,5 +1,5 @@
 use std::cmp::PartialEq;
-use std::ops::{Add, Div, Mul, Sub};
+use std::ops::{Add, Div, Mul, Neg, Sub};
 
 #[derive(Clone, Copy, Debug)]
 pub struct Vector2 {
@@ -23,9 +23,48 @@ impl Vector2 {
         position_diff / magnitude * max_distance_delta + position
     }
 
-    pub(crate) fn magnitude(self: Self) -> f64 {
+    /// Point `t` of the way from `from` to `to`.
+    pub fn lerp(from: Self, to: Self, t: f64) -> Self {
+        from + (to - from) * t
+    }
+
+    pub fn magnitude(self) -> f64 {
         (self.x * self.x + self.y * self.y).sqrt()
     }
+
+    pub fn dot(self, other: Self) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    pub fn cross(self, other: Self) -> f64 {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// Vector rotated 90 degrees counter-clockwise.
+    pub fn perpendicular(self) -> Self {
+        Vector2::new(-self.y, self.x)
+    }
+
+    pub fn normalized(self) -> Self {
+        let magnitude = self.magnitude();
+        if magnitude == 0.0 {
+            return self;
+        }
+        self / magnitude
+    }
+
+    /// Same direction, shortened to `max_magnitude` if it is longer.
+    pub fn truncated(self, max_magnitude: f64) -> Self {
+        if self.magnitude() > max_magnitude {
+            return self.normalized() * max_magnitude;
+        }
+        self
+    }
+
+    pub fn rotated(self, angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Vector2::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+    }
 }
 
 impl PartialEq for Vector2 {
@@ -34,6 +73,16 @@ impl PartialEq for Vector2 {
     }
 }
 
+impl Neg for Vector2 {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Vector2 {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
 impl Div<f64> for Vector2 {
     type Output = Self;
     fn div(self, other: f64) -> Self {
@@ -169,6 +218,73 @@ mod tests {
     }
 
     #[test]
+    fn lerp_halfway_returns_midpoint() {
+        let from = Vector2::new(0.0, 2.0);
+        let to = Vector2::new(4.0, 6.0);
+
+        let result = Vector2::lerp(from, to, 0.5);
+
+        assert_eq!(result, Vector2::new(2.0, 4.0));
+    }
+
+    #[test]
+    fn dot_perpendicular_vectors_returns_zero() {
+        let vector = Vector2::new(2.0, 3.0);
+
+        let result = vector.dot(vector.perpendicular());
+
+        assert_eq!(result, 0.0);
+    }
+
+    #[test]
+    fn cross_counter_clockwise_vectors_returns_positive() {
+        let vector = Vector2::new(1.0, 0.0);
+        let other = Vector2::new(0.0, 1.0);
+
+        let result = vector.cross(other);
+
+        assert_eq!(result, 1.0);
+    }
+
+    #[test]
+    fn normalized_vector_has_unit_magnitude() {
+        let vector = Vector2::new(3.0, 4.0);
+
+        let result = vector.normalized();
+
+        assert_eq!(result, Vector2::new(0.6, 0.8));
+    }
+
+    #[test]
+    fn normalized_zero_vector_returns_zero_vector() {
+        let vector = Vector2::new(0.0, 0.0);
+
+        let result = vector.normalized();
+
+        assert_eq!(result, Vector2::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn truncated_long_vector_returns_max_magnitude() {
+        let vector = Vector2::new(3.0, 4.0);
+
+        let result = vector.truncated(2.5);
+
+        assert_eq!(result, Vector2::new(1.5, 2.0));
+        assert_eq!(vector.truncated(10.0), vector);
+    }
+
+    #[test]
+    fn rotated_quarter_turn_rotates_counter_clockwise() {
+        let vector = Vector2::new(1.0, 0.0);
+
+        let result = vector.rotated(std::f64::consts::FRAC_PI_2);
+
+        assert!((result - Vector2::new(0.0, 1.0)).magnitude() < 1e-12);
+    }
+
+    #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn partial_eq_position_by_equal_position() {
         let position = Vector2::new(2.0, 2.0);
         let equal_position = Vector2::new(2.0, 2.0);
@@ -179,6 +295,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn partial_eq_position_by_non_equal_position() {
         let position = Vector2::new(2.0, 2.0);
         let non_equal_position = Vector2::new(0.0, 0.0);
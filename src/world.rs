@@ -0,0 +1,811 @@
+use crate::arena::Arena;
+use crate::broad_phase::SpatialHash;
+use crate::chaser::{Chaser, ChaserKind};
+use crate::collider::Collider;
+use crate::collision_events::ContactTracker;
+use crate::components::{ContactDamage, Health, Sprite};
+use crate::config::Settings;
+use crate::ecs::{Components, Entities, Entity};
+use crate::input::Input;
+use crate::layer;
+use crate::navigation::NavGrid;
+use crate::physics::RigidBody;
+use crate::player::Player;
+use crate::proximity::{NearMiss, ProximityTracker};
+use crate::score::Score;
+use crate::settings;
+use crate::spawner::{ChaserSpawn, Spawner, WaveConfig};
+use crate::systems;
+use crate::timestep::FixedTimestep;
+use crate::vector2::Vector2;
+
+/// The whole game simulation, steppable without a window or renderer.
+///
+/// Entities are handles into the component storages below, and each step
+/// runs the systems over whichever entities have the components they need.
+pub struct World {
+    entities: Entities,
+    pub colliders: Components<Collider>,
+    pub bodies: Components<RigidBody>,
+    /// Collider positions before the last step, for sweeps and rendering.
+    pub previous_positions: Components<Vector2>,
+    pub sprites: Components<Sprite>,
+    pub healths: Components<Health>,
+    pub contact_damages: Components<ContactDamage>,
+    pub players: Components<Player>,
+    pub chasers: Components<Chaser>,
+    pub scores: Components<Score>,
+    width: f64,
+    height: f64,
+    arena: Arena,
+    navigation: NavGrid,
+    spawner: Spawner,
+    broad_phase: SpatialHash,
+    contacts: ContactTracker<Entity>,
+    proximity: ProximityTracker<Entity>,
+    /// Near misses of the last step.
+    near_misses: Vec<NearMiss<Entity>>,
+    settings: Settings,
+    timestep: FixedTimestep,
+}
+
+impl World {
+    pub fn new(width: f64, height: f64) -> Self {
+        World::with_settings(width, height, Settings::default())
+    }
+
+    /// World whose players, chasers and waves follow the given settings.
+    pub fn with_settings(width: f64, height: f64, settings: Settings) -> Self {
+        let waves = WaveConfig::from_settings(&settings.waves, &settings.chaser);
+        World::build(width, height, settings, waves)
+    }
+
+    /// World whose chasers come in the given waves.
+    pub fn with_waves(width: f64, height: f64, waves: WaveConfig) -> Self {
+        World::build(width, height, Settings::default(), waves)
+    }
+
+    fn build(width: f64, height: f64, settings: Settings, waves: WaveConfig) -> Self {
+        let mut world = World {
+            entities: Entities::new(),
+            colliders: Components::new(),
+            bodies: Components::new(),
+            previous_positions: Components::new(),
+            sprites: Components::new(),
+            healths: Components::new(),
+            contact_damages: Components::new(),
+            players: Components::new(),
+            chasers: Components::new(),
+            scores: Components::new(),
+            width,
+            height,
+            arena: Arena::new(width, height),
+            navigation: NavGrid::new(
+                width,
+                height,
+                settings::navigation::CELL_SIZE,
+                settings::navigation::CLEARANCE,
+                &[],
+            ),
+            spawner: Spawner::new(waves, width, height),
+            broad_phase: SpatialHash::new(settings::collision::CELL_SIZE),
+            contacts: ContactTracker::new(),
+            proximity: ProximityTracker::new(),
+            near_misses: Vec::new(),
+            settings,
+            timestep: FixedTimestep::new(
+                settings::simulation::STEP,
+                settings::simulation::MAX_STEPS_PER_FRAME,
+            ),
+        };
+        for &(x, y, half_width, half_height) in &settings::arena::OBSTACLES {
+            let center = Vector2::new(x * width, y * height);
+            world.add_obstacle(Collider::new_box(center, half_width, half_height, 0.0));
+        }
+        world.spawn_player(Vector2::new(width / 2.0, height / 2.0));
+        world
+    }
+
+    /// Adds a static collider that blocks movement and that chasers find
+    /// their way around.
+    pub fn add_obstacle(&mut self, obstacle: Collider) {
+        self.arena.add_wall(obstacle);
+        self.navigation = NavGrid::new(
+            self.width,
+            self.height,
+            settings::navigation::CELL_SIZE,
+            settings::navigation::CLEARANCE,
+            self.arena.walls(),
+        );
+    }
+
+    pub fn arena(&self) -> &Arena {
+        &self.arena
+    }
+
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    /// Switches to new settings in the middle of a game. Players and chasers
+    /// already about take on the new sizes, speeds, colours, physics and
+    /// damage, chasers keeping however much bigger and faster their wave
+    /// made them. Later waves follow the new wave settings. Health, lives
+    /// and the window size only change with the next game.
+    pub fn apply_settings(&mut self, settings: Settings) {
+        let previous = std::mem::replace(&mut self.settings, settings);
+        let Settings {
+            color,
+            player,
+            chaser,
+            ..
+        } = self.settings;
+
+        for (entity, _) in self.players.iter() {
+            if let Some(collider) = self.colliders.get_mut(entity) {
+                collider.set_radius(player.size);
+            }
+            if let Some(sprite) = self.sprites.get_mut(entity) {
+                *sprite = Sprite::new(with_alpha(color.player, sprite.color), player.size);
+            }
+            if let Some(body) = self.bodies.get_mut(entity) {
+                body.set_mass(player.mass);
+                body.drag = player.drag();
+                body.restitution = player.restitution;
+            }
+            if let Some(score) = self.scores.get_mut(entity) {
+                score.set_settings(self.settings.score);
+            }
+        }
+
+        let speed_scale = chaser.speed / previous.chaser.speed;
+        let size_scale = chaser.size / previous.chaser.size;
+        for (entity, state) in self.chasers.iter_mut() {
+            state.speed *= speed_scale;
+            if let Some(sprite) = self.sprites.get_mut(entity) {
+                let size = sprite.radius * size_scale;
+                *sprite = Sprite::new(with_alpha(color.chaser, sprite.color), size);
+                if let Some(collider) = self.colliders.get_mut(entity) {
+                    collider.set_radius(size);
+                }
+            }
+            if let Some(body) = self.bodies.get_mut(entity) {
+                body.set_mass(chaser.mass);
+                body.drag = chaser.drag();
+                body.restitution = chaser.restitution;
+            }
+            if let Some(damage) = self.contact_damages.get_mut(entity) {
+                damage.amount = chaser.damage;
+            }
+        }
+
+        self.spawner.set_config(WaveConfig::from_settings(
+            &self.settings.waves,
+            &self.settings.chaser,
+        ));
+    }
+
+    pub fn spawn_player(&mut self, position: Vector2) -> Entity {
+        let entity = self.entities.create();
+        let player = self.settings.player;
+        let mut collider = Collider::new(position, player.size);
+        collider.set_layer(layer::PLAYER);
+        collider.set_mask(layer::ENEMY | layer::PROJECTILE | layer::PICKUP | layer::WALL);
+        self.colliders.insert(entity, collider);
+        self.bodies.insert(
+            entity,
+            RigidBody::new(player.mass, player.drag(), player.restitution),
+        );
+        self.sprites
+            .insert(entity, Sprite::new(self.settings.color.player, player.size));
+        self.healths.insert(
+            entity,
+            Health::with_invulnerability(player.health, player.invulnerable_time),
+        );
+        self.players
+            .insert(entity, Player::with_lives(player.lives));
+        self.scores
+            .insert(entity, Score::with_settings(self.settings.score));
+        entity
+    }
+
+    /// Chaser with the speed and size from the chaser settings.
+    pub fn spawn_chaser(&mut self, position: Vector2) -> Entity {
+        self.spawn_chaser_from(ChaserSpawn {
+            position,
+            speed: self.settings.chaser.speed,
+            size: self.settings.chaser.size,
+            kind: ChaserKind::Brute,
+        })
+    }
+
+    pub fn spawn_chaser_from(&mut self, spawn: ChaserSpawn) -> Entity {
+        let entity = self.entities.create();
+        let chaser = self.settings.chaser;
+        let mut collider = Collider::new(spawn.position, spawn.size);
+        collider.set_layer(layer::ENEMY);
+        collider.set_mask(layer::PLAYER | layer::PROJECTILE | layer::WALL);
+        self.colliders.insert(entity, collider);
+        self.bodies.insert(
+            entity,
+            RigidBody::new(chaser.mass, chaser.drag(), chaser.restitution),
+        );
+        self.sprites
+            .insert(entity, Sprite::new(self.settings.color.chaser, spawn.size));
+        self.contact_damages.insert(
+            entity,
+            ContactDamage {
+                amount: chaser.damage,
+            },
+        );
+        self.chasers.insert(
+            entity,
+            Chaser::of_kind(spawn.kind, spawn.speed, entity.index() as u64),
+        );
+        entity
+    }
+
+    /// Removes the entity and all its components, returns false if it was
+    /// already gone.
+    pub fn despawn(&mut self, entity: Entity) -> bool {
+        if !self.entities.destroy(entity) {
+            return false;
+        }
+        self.colliders.remove(entity);
+        self.bodies.remove(entity);
+        self.previous_positions.remove(entity);
+        self.sprites.remove(entity);
+        self.healths.remove(entity);
+        self.contact_damages.remove(entity);
+        self.players.remove(entity);
+        self.chasers.remove(entity);
+        self.scores.remove(entity);
+        self.contacts.remove(entity);
+        self.proximity.remove(entity);
+        true
+    }
+
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.entities.is_alive(entity)
+    }
+
+    /// Number of waves started so far.
+    pub fn wave(&self) -> u32 {
+        self.spawner.get_wave()
+    }
+
+    /// Whether every player is dead with no lives left.
+    pub fn is_game_over(&self) -> bool {
+        !self.players.is_empty() && self.players.iter().all(|(_, player)| player.is_out())
+    }
+
+    /// Score of the first player, if there is one.
+    pub fn score(&self) -> Option<&Score> {
+        self.scores.get(self.player()?)
+    }
+
+    /// Chasers that passed close to a player without touching in the last
+    /// step.
+    pub fn near_misses(&self) -> &[NearMiss<Entity>] {
+        &self.near_misses
+    }
+
+    /// The first player entity, if there is one.
+    pub fn player(&self) -> Option<Entity> {
+        self.players.iter().map(|(entity, _)| entity).next()
+    }
+
+    pub fn position(&self, entity: Entity) -> Option<Vector2> {
+        self.colliders
+            .get(entity)
+            .map(|collider| collider.get_position())
+    }
+
+    /// Position `alpha()` of the way from the previous to the current step,
+    /// for rendering between simulation steps.
+    pub fn interpolated_position(&self, entity: Entity) -> Option<Vector2> {
+        let position = self.position(entity)?;
+        Some(match self.previous_positions.get(entity) {
+            Some(&previous) => Vector2::lerp(previous, position, self.alpha()),
+            None => position,
+        })
+    }
+
+    pub fn set_input(&mut self, input: Input) {
+        for (_, player) in self.players.iter_mut() {
+            player.input(input.left, input.right, input.up, input.down);
+        }
+    }
+
+    /// Adds a frame's worth of time and runs however many fixed steps fit.
+    pub fn update(&mut self, dt: f64) {
+        for _ in 0..self.timestep.advance(dt) {
+            self.step(self.timestep.get_step());
+        }
+    }
+
+    /// How far into the next step rendering should blend.
+    pub fn alpha(&self) -> f64 {
+        self.timestep.alpha()
+    }
+
+    /// Runs a single simulation step of `dt` seconds.
+    pub fn step(&mut self, dt: f64) {
+        systems::invulnerability(&mut self.healths, &mut self.sprites, dt);
+        for spawn in self.spawner.update(dt) {
+            self.spawn_chaser_from(spawn);
+        }
+        systems::player_control(
+            &self.players,
+            &mut self.bodies,
+            self.settings.player.acceleration,
+        );
+        systems::think(
+            &mut self.chasers,
+            &self.players,
+            &self.colliders,
+            &self.bodies,
+            &self.arena,
+            dt,
+        );
+        systems::steer(
+            &mut self.chasers,
+            &self.players,
+            &self.colliders,
+            &mut self.bodies,
+            &self.arena,
+            &self.navigation,
+            dt,
+        );
+        systems::integrate(
+            &mut self.bodies,
+            &mut self.colliders,
+            &mut self.previous_positions,
+            dt,
+        );
+        systems::confine(&self.arena, &mut self.colliders, &mut self.bodies);
+        let events = systems::detect_collisions(
+            &self.colliders,
+            &self.previous_positions,
+            &mut self.broad_phase,
+            &mut self.contacts,
+        );
+        let hurt = systems::contact_damage(&events, &self.contact_damages, &mut self.healths);
+        self.near_misses = systems::near_misses(
+            &self.players,
+            &self.chasers,
+            &self.colliders,
+            &mut self.proximity,
+            self.settings.score.near_miss_margin,
+        );
+        systems::scoring(
+            &mut self.scores,
+            &self.players,
+            &self.near_misses,
+            &hurt,
+            dt,
+        );
+        systems::player_lives(
+            &mut self.players,
+            &mut self.healths,
+            &mut self.colliders,
+            &mut self.bodies,
+            Vector2::new(self.width / 2.0, self.height / 2.0),
+            self.settings.player.respawn_time,
+            dt,
+        );
+        systems::stun_on_contact(&events, &mut self.chasers, &self.players);
+        systems::resolve_contacts(&events, &self.arena, &mut self.colliders, &mut self.bodies);
+    }
+}
+
+/// `color` with the alpha of `current`, which may be fading while the
+/// entity is invulnerable.
+fn with_alpha(color: [f32; 4], current: [f32; 4]) -> [f32; 4] {
+    [color[0], color[1], color[2], current[3]]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ai::AiState;
+    use crate::collider::Collider;
+    use crate::config::Settings;
+    use crate::input::{Input, KeyState};
+    use crate::settings;
+    use crate::spawner::WaveConfig;
+    use crate::vector2::Vector2;
+    use crate::world::World;
+
+    const WIDTH: f64 = 1024.0;
+    const HEIGHT: f64 = 512.0;
+
+    fn run(world: &mut World, seconds: f64) {
+        let steps = (seconds / settings::simulation::STEP).round() as u32;
+        for _ in 0..steps {
+            world.step(settings::simulation::STEP);
+        }
+    }
+
+    /// World without any waves of chasers.
+    fn quiet_world() -> World {
+        let waves = WaveConfig {
+            spawn_points: Vec::new(),
+            ..WaveConfig::default()
+        };
+        World::with_waves(WIDTH, HEIGHT, waves)
+    }
+
+    fn kill_player(world: &mut World) {
+        let player = world.player().unwrap();
+        world
+            .healths
+            .get_mut(player)
+            .unwrap()
+            .damage(settings::player::HEALTH);
+        world.step(settings::simulation::STEP);
+    }
+
+    fn player_position(world: &World) -> Vector2 {
+        world.position(world.player().unwrap()).unwrap()
+    }
+
+    fn chaser_position(world: &World) -> Vector2 {
+        let (chaser, _) = world.chasers.iter().next().unwrap();
+        world.position(chaser).unwrap()
+    }
+
+    #[test]
+    fn new_player_starts_in_center() {
+        let world = World::new(WIDTH, HEIGHT);
+
+        let result = player_position(&world);
+
+        assert_eq!(result, Vector2::new(WIDTH / 2.0, HEIGHT / 2.0));
+    }
+
+    #[test]
+    fn step_right_held_moves_player_right() {
+        let mut world = World::new(WIDTH, HEIGHT);
+        let mut input = Input::new();
+        input.right = KeyState::Pressed;
+
+        world.set_input(input);
+        run(&mut world, 0.5);
+
+        let position = player_position(&world);
+        assert!(position.x > WIDTH / 2.0);
+        assert_eq!(position.y, HEIGHT / 2.0);
+    }
+
+    #[test]
+    fn step_chaser_closes_in_on_player() {
+        let mut world = World::new(WIDTH, HEIGHT);
+        let size = settings::chaser::SIZE;
+        world.spawn_chaser(Vector2::new(size, size));
+        let start = chaser_position(&world);
+        let target = player_position(&world);
+
+        run(&mut world, 0.5);
+
+        let position = chaser_position(&world);
+        assert!((target - position).magnitude() < (target - start).magnitude());
+    }
+
+    #[test]
+    fn step_held_key_keeps_player_inside_arena() {
+        let mut world = World::new(WIDTH, HEIGHT);
+        let mut input = Input::new();
+        input.up = KeyState::Pressed;
+
+        world.set_input(input);
+        run(&mut world, 5.0);
+
+        let position = player_position(&world);
+        assert!(position.y >= settings::player::SIZE - 1e-9);
+    }
+
+    #[test]
+    fn step_chaser_touching_player_costs_one_health() {
+        let mut world = World::new(WIDTH, HEIGHT);
+        let player = world.player().unwrap();
+        world.spawn_chaser(Vector2::new(WIDTH / 2.0 + 40.0, HEIGHT / 2.0));
+
+        world.step(settings::simulation::STEP);
+        world.step(settings::simulation::STEP);
+
+        let health = world.healths.get(player).unwrap();
+        assert_eq!(
+            health.get_current(),
+            settings::player::HEALTH - settings::chaser::DAMAGE
+        );
+    }
+
+    #[test]
+    fn step_waves_bring_in_more_chasers_over_time() {
+        let mut world = World::new(WIDTH, HEIGHT);
+
+        world.step(settings::simulation::STEP);
+        let first_wave = world.chasers.len();
+        run(&mut world, settings::waves::INTERVAL + 1.0);
+
+        assert_eq!(first_wave, 1);
+        assert_eq!(world.wave(), 2);
+        assert!(world.chasers.len() > first_wave);
+    }
+
+    #[test]
+    fn step_chaser_behind_obstacle_goes_around_it() {
+        let mut world = quiet_world();
+        world.add_obstacle(Collider::new_box(
+            Vector2::new(400.0, HEIGHT / 2.0),
+            10.0,
+            150.0,
+            0.0,
+        ));
+        world.spawn_chaser(Vector2::new(330.0, HEIGHT / 2.0));
+
+        run(&mut world, 4.0);
+
+        let distance = (player_position(&world) - chaser_position(&world)).magnitude();
+        assert!(distance < 100.0);
+    }
+
+    #[test]
+    fn step_chaser_touching_player_gets_stunned() {
+        let mut world = World::new(WIDTH, HEIGHT);
+        let chaser = world.spawn_chaser(Vector2::new(WIDTH / 2.0 + 40.0, HEIGHT / 2.0));
+
+        world.step(settings::simulation::STEP);
+        world.step(settings::simulation::STEP);
+
+        let brain = &world.chasers.get(chaser).unwrap().brain;
+        assert_eq!(brain.get_state(), AiState::Stunned);
+    }
+
+    #[test]
+    fn step_chaser_staying_on_player_hurts_once_while_invulnerable() {
+        let mut world = quiet_world();
+        let player = world.player().unwrap();
+        world.spawn_chaser(Vector2::new(WIDTH / 2.0 + 40.0, HEIGHT / 2.0));
+
+        run(&mut world, settings::player::INVULNERABLE_TIME / 2.0);
+
+        let health = world.healths.get(player).unwrap();
+        assert_eq!(health.get_current(), settings::player::HEALTH - 1);
+        assert!(health.is_invulnerable());
+    }
+
+    #[test]
+    fn step_health_depleted_kills_player() {
+        let mut world = quiet_world();
+        let player = world.player().unwrap();
+
+        kill_player(&mut world);
+
+        let state = world.players.get(player).unwrap();
+        assert!(!state.is_alive());
+        assert_eq!(state.get_lives(), settings::player::LIVES - 1);
+        assert!(!world.colliders.get(player).unwrap().is_enabled());
+        assert!(!world.is_game_over());
+    }
+
+    #[test]
+    fn step_dead_player_respawns_in_center_with_full_health() {
+        let mut world = quiet_world();
+        let player = world.player().unwrap();
+        let mut input = Input::new();
+        input.right = KeyState::Pressed;
+        world.set_input(input);
+        run(&mut world, 0.5);
+
+        kill_player(&mut world);
+        // One step extra so adding up steps can't fall just short.
+        run(
+            &mut world,
+            settings::player::RESPAWN_TIME + settings::simulation::STEP,
+        );
+
+        let health = world.healths.get(player).unwrap();
+        assert!(world.players.get(player).unwrap().is_alive());
+        assert_eq!(health.get_current(), settings::player::HEALTH);
+        assert!(health.is_invulnerable());
+        assert!(world.colliders.get(player).unwrap().is_enabled());
+        assert_eq!(
+            player_position(&world),
+            Vector2::new(WIDTH / 2.0, HEIGHT / 2.0)
+        );
+    }
+
+    #[test]
+    fn step_last_life_lost_is_game_over() {
+        let mut world = quiet_world();
+
+        // Long enough to respawn and for the invulnerability to wear off.
+        let respawn = settings::player::RESPAWN_TIME + settings::player::INVULNERABLE_TIME + 0.5;
+        for _ in 0..settings::player::LIVES {
+            run(&mut world, respawn);
+            kill_player(&mut world);
+        }
+        run(&mut world, respawn);
+
+        assert!(world.is_game_over());
+        assert!(!world
+            .players
+            .get(world.player().unwrap())
+            .unwrap()
+            .is_alive());
+    }
+
+    #[test]
+    fn step_surviving_scores_points() {
+        let mut world = quiet_world();
+
+        run(&mut world, 1.0);
+
+        let score = world.score().unwrap();
+        assert!(score.get_points() > 0);
+        assert!(score.get_time_survived() > 0.9);
+    }
+
+    #[test]
+    fn step_chaser_leaving_untouched_scores_near_miss() {
+        let mut world = quiet_world();
+        let gap = settings::score::NEAR_MISS_MARGIN / 2.0;
+        let offset = settings::player::SIZE + settings::chaser::SIZE + gap;
+        let chaser = world.spawn_chaser(player_position(&world) + Vector2::new(offset, 0.0));
+
+        world.step(settings::simulation::STEP);
+        world
+            .colliders
+            .get_mut(chaser)
+            .unwrap()
+            .set_position(Vector2::new(100.0, 100.0));
+        world.step(settings::simulation::STEP);
+
+        let near_misses = world.near_misses();
+        assert_eq!(near_misses.len(), 1);
+        assert!(near_misses[0].a == chaser || near_misses[0].b == chaser);
+        assert!((near_misses[0].closest - gap).abs() < 1.0);
+        assert_eq!(world.score().unwrap().get_streak(), 1);
+    }
+
+    #[test]
+    fn step_player_dying_near_chaser_is_no_near_miss() {
+        let mut world = quiet_world();
+        let gap = settings::score::NEAR_MISS_MARGIN / 2.0;
+        let offset = settings::player::SIZE + settings::chaser::SIZE + gap;
+        world.spawn_chaser(player_position(&world) + Vector2::new(offset, 0.0));
+        world.step(settings::simulation::STEP);
+
+        kill_player(&mut world);
+        let mut near_misses = Vec::new();
+        for _ in 0..3 {
+            world.step(settings::simulation::STEP);
+            near_misses.extend_from_slice(world.near_misses());
+        }
+
+        assert!(!world
+            .players
+            .get(world.player().unwrap())
+            .unwrap()
+            .is_alive());
+        assert!(near_misses.is_empty());
+    }
+
+    #[test]
+    fn step_dead_player_scores_nothing() {
+        let mut world = quiet_world();
+        kill_player(&mut world);
+        let before = world.score().unwrap().get_time_survived();
+
+        run(&mut world, 1.0);
+
+        assert_eq!(world.score().unwrap().get_time_survived(), before);
+    }
+
+    #[test]
+    fn with_settings_spawns_player_and_chasers_by_settings() {
+        let mut settings = Settings::default();
+        settings.player.size = 12.0;
+        settings.player.lives = 7;
+        settings.chaser.damage = 2;
+        let mut world = World::with_settings(WIDTH, HEIGHT, settings);
+
+        let chaser = world.spawn_chaser(Vector2::new(100.0, 100.0));
+
+        let player = world.player().unwrap();
+        assert_eq!(world.sprites.get(player).unwrap().radius, 12.0);
+        assert_eq!(world.players.get(player).unwrap().get_lives(), 7);
+        assert_eq!(world.contact_damages.get(chaser).unwrap().amount, 2);
+    }
+
+    #[test]
+    fn apply_settings_changes_player_and_chasers_already_about() {
+        let mut world = World::new(WIDTH, HEIGHT);
+        let player = world.player().unwrap();
+        let chaser = world.spawn_chaser(Vector2::new(100.0, 100.0));
+        let mut settings = world.settings().clone();
+        settings.player.size = 10.0;
+        settings.player.speed = 500.0;
+        settings.chaser.size = settings::chaser::SIZE * 2.0;
+        settings.chaser.speed = settings::chaser::SPEED / 2.0;
+        settings.chaser.damage = 3;
+
+        world.apply_settings(settings.clone());
+
+        assert_eq!(world.sprites.get(player).unwrap().radius, 10.0);
+        assert_eq!(
+            world.bodies.get(player).unwrap().drag,
+            settings.player.drag()
+        );
+        assert_eq!(
+            world.sprites.get(chaser).unwrap().radius,
+            settings::chaser::SIZE * 2.0
+        );
+        assert_eq!(
+            world.chasers.get(chaser).unwrap().speed,
+            settings::chaser::SPEED / 2.0
+        );
+        assert_eq!(world.contact_damages.get(chaser).unwrap().amount, 3);
+        let probe = Collider::new(Vector2::new(100.0, 170.0), 1.0);
+        assert_eq!(world.colliders.get(chaser).unwrap().distance(&probe), 9.0);
+    }
+
+    #[test]
+    fn apply_settings_faster_player_moves_faster() {
+        let mut slow = World::new(WIDTH, HEIGHT);
+        let mut fast = World::new(WIDTH, HEIGHT);
+        let mut settings = fast.settings().clone();
+        settings.player.speed *= 2.0;
+        fast.apply_settings(settings);
+        let input = Input {
+            right: KeyState::Pressed,
+            ..Input::new()
+        };
+        slow.set_input(input);
+        fast.set_input(input);
+
+        for _ in 0..48 {
+            slow.step(settings::simulation::STEP);
+            fast.step(settings::simulation::STEP);
+        }
+
+        let slow_speed = slow.bodies.get(slow.player().unwrap()).unwrap().velocity.x;
+        let fast_speed = fast.bodies.get(fast.player().unwrap()).unwrap().velocity.x;
+        assert!(fast_speed > slow_speed * 1.5);
+    }
+
+    #[test]
+    fn despawn_removes_entity_and_its_components() {
+        let mut world = World::new(WIDTH, HEIGHT);
+        let chaser = world.spawn_chaser(Vector2::new(100.0, 100.0));
+
+        let first = world.despawn(chaser);
+        let second = world.despawn(chaser);
+
+        assert!(first);
+        assert!(!second);
+        assert!(!world.is_alive(chaser));
+        assert!(world.position(chaser).is_none());
+        assert!(!world.chasers.contains(chaser));
+    }
+
+    #[test]
+    fn update_same_total_time_in_different_frames_gives_same_state() {
+        let step = settings::simulation::STEP;
+        let mut steady = World::new(WIDTH, HEIGHT);
+        let mut hitchy = World::new(WIDTH, HEIGHT);
+
+        for _ in 0..64 {
+            steady.update(step);
+        }
+        for _ in 0..8 {
+            for &steps in &[1.5, 0.5, 3.0, 2.0, 0.25, 0.75] {
+                hitchy.update(step * steps);
+            }
+        }
+
+        assert_eq!(player_position(&steady), player_position(&hitchy));
+        assert_eq!(chaser_position(&steady), chaser_position(&hitchy));
+    }
+}